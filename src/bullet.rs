@@ -0,0 +1,773 @@
+use std::collections::hash_map::RandomState;
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::{BuildHasher, Hasher};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, TimeZone};
+use chrono_tz::Tz;
+
+/// Meeting length assumed when a `[mtg]` marker has no duration.
+pub(crate) const DEFAULT_MEETING_MIN: u32 = 60;
+
+/// What a bullet is: the rapid-logging signifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+	/// `- [ ]` and friends: something to do.
+	Task,
+	/// `- [o]`: something that happens (or happened) on the day.
+	Event,
+	/// `- text`: a plain note.
+	Note,
+}
+
+/// Where a bullet stands. Only tasks move through states; events and notes
+/// are always `Open`, since their markers have no room for one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum State {
+	Open,
+	/// `- [x]`
+	Done,
+	/// `- [>]`: moved to another day.
+	Migrated,
+	/// `- [<]`: moved to a future log.
+	Scheduled,
+	/// `- [-]` (or `- [~]`): no longer relevant.
+	Cancelled,
+}
+
+impl Kind {
+	/// Lowercase name used in queries and exports.
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Kind::Task => "task",
+			Kind::Event => "event",
+			Kind::Note => "note",
+		}
+	}
+}
+
+impl State {
+	/// Lowercase name used in queries and exports.
+	pub fn as_str(self) -> &'static str {
+		match self {
+			State::Open => "open",
+			State::Done => "done",
+			State::Migrated => "migrated",
+			State::Scheduled => "scheduled",
+			State::Cancelled => "cancelled",
+		}
+	}
+}
+
+/// A single bullet parsed from a day file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bullet {
+	pub line_index: usize, // index in file content lines
+	pub visible_index: usize, // 1-based index among bullet lines
+	pub id: Option<String>, // persistent `^id` marker, absent on bullets written before IDs existed
+	pub kind: Kind,
+	pub state: State,
+	pub text: String,
+	pub priority: Option<u8>,
+	pub tags: Vec<String>,
+	pub notes: Vec<String>,
+	pub meeting_time: Option<NaiveTime>,
+	pub meeting_duration_min: Option<u32>,
+	pub meeting_tz: Option<Tz>, // IANA zone the meeting time is written in; local time when absent
+	pub recurring: bool, // occurrence of a series from `recurring.md`
+	pub series: Option<String>, // `↻ id`: series an occurrence written to a day file belongs to
+	pub migrated_from: Option<NaiveDate>, // `← YYYY-MM-DD`: day this bullet was migrated from
+	pub migrated_to: Option<NaiveDate>, // `→ YYYY-MM-DD`: day a `[>]` bullet was migrated to
+	pub migrated_from_month: Option<NaiveDate>, // `← YYYY-MM`: month page this bullet was migrated from (its first day)
+	pub migrated_to_month: Option<NaiveDate>, // `→ YYYY-MM`: month page a `[>]` bullet was migrated to (its first day)
+	pub migrated_from_collection: Option<String>, // `← @name`: collection this bullet was migrated from
+	pub migrated_to_collection: Option<String>, // `→ @name`: collection a `[>]` bullet was migrated to
+}
+
+impl Bullet {
+	/// An open task: what migration carries forward and progress counts as outstanding.
+	pub fn is_open(&self) -> bool {
+		self.kind == Kind::Task && self.state == State::Open
+	}
+
+	pub fn is_done(&self) -> bool {
+		self.state == State::Done
+	}
+
+	/// Tasks that count toward a day's progress: open or done, not moved or cancelled.
+	pub fn is_actionable(&self) -> bool {
+		self.kind == Kind::Task && matches!(self.state, State::Open | State::Done)
+	}
+
+	/// True when the bullet carries every tag in `tags` and, if given, the priority.
+	pub fn matches(&self, tags: &[String], priority: Option<u8>) -> bool {
+		if let Some(p) = priority {
+			if self.priority != Some(p) { return false; }
+		}
+		tags.iter().all(|t| self.tags.iter().any(|bt| bt == t))
+	}
+
+	/// Render the bullet line (without notes) in the on-disk format.
+	pub(crate) fn to_line(&self) -> String {
+		let mut line = String::from(marker(self.kind, self.state));
+		if let Some(t) = self.meeting_time {
+			line.push_str(&meeting_prefix(t, self.meeting_duration_min, self.meeting_tz));
+		}
+		line.push_str(priority_prefix(self.priority));
+		line.push_str(self.text.trim());
+		for t in &self.tags { line.push_str(&format!(" #{}", t)); }
+		if let Some(series) = &self.series { line.push_str(&format!(" ↻ {}", series)); }
+		if let Some(link) = self.came_from() { line.push_str(&format!(" ← {}", link)); }
+		if let Some(link) = self.went_to() { line.push_str(&format!(" → {}", link)); }
+		if let Some(id) = &self.id { line.push_str(&format!(" ^{}", id)); }
+		line
+	}
+
+	/// Page this bullet was migrated from, if it records one.
+	pub(crate) fn came_from(&self) -> Option<Link> {
+		Link::either(self.migrated_from, self.migrated_from_month, &self.migrated_from_collection)
+	}
+
+	/// Page a `[>]` or `[<]` bullet was migrated to, if it records one.
+	pub(crate) fn went_to(&self) -> Option<Link> {
+		Link::either(self.migrated_to, self.migrated_to_month, &self.migrated_to_collection)
+	}
+
+	/// The bullet line followed by its `  - note:` lines, as written in a day file.
+	pub fn block(&self) -> Vec<String> {
+		let mut block = vec![self.to_line()];
+		block.extend(self.notes.iter().map(|n| format!("  - note: {}", n)));
+		block
+	}
+
+	/// Bullet text with the meeting prefix restored, suitable for re-adding elsewhere.
+	pub fn full_text(&self) -> String {
+		let mut full_text = String::new();
+		if let Some(t) = self.meeting_time {
+			full_text.push_str(&meeting_prefix(t, self.meeting_duration_min, self.meeting_tz));
+		}
+		full_text.push_str(&self.text);
+		full_text
+	}
+
+	/// When a meeting written on `date` starts in the viewer's local time.
+	pub fn meeting_start(&self, date: NaiveDate) -> Option<NaiveDateTime> {
+		let start = date.and_time(self.meeting_time?);
+		Some(match self.meeting_tz {
+			Some(tz) => to_local(start, tz),
+			None => start,
+		})
+	}
+
+	/// When a meeting written on `date` ends in local time. Meetings may run
+	/// past midnight or over several days.
+	pub fn meeting_end(&self, date: NaiveDate) -> Option<NaiveDateTime> {
+		let minutes = self.meeting_duration_min.unwrap_or(DEFAULT_MEETING_MIN);
+		Some(self.meeting_start(date)? + TimeDelta::minutes(minutes.into()))
+	}
+}
+
+/// The other end of a migration: a day, a month page (`YYYY-MM`, held as
+/// its first day), or a named collection (`@name`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Link {
+	Day(NaiveDate),
+	Month(NaiveDate),
+	Collection(String),
+}
+
+impl Link {
+	fn either(day: Option<NaiveDate>, month: Option<NaiveDate>, collection: &Option<String>) -> Option<Link> {
+		day.map(Link::Day).or(month.map(Link::Month)).or_else(|| collection.clone().map(Link::Collection))
+	}
+
+	/// Split into the `(day, month, collection)` fields stored on a [`Bullet`].
+	pub(crate) fn split(link: Option<Link>) -> (Option<NaiveDate>, Option<NaiveDate>, Option<String>) {
+		match link {
+			Some(Link::Day(d)) => (Some(d), None, None),
+			Some(Link::Month(m)) => (None, Some(m), None),
+			Some(Link::Collection(name)) => (None, None, Some(name)),
+			None => (None, None, None),
+		}
+	}
+}
+
+impl fmt::Display for Link {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Link::Day(d) => write!(f, "{}", d),
+			Link::Month(m) => write!(f, "{}", m.format("%Y-%m")),
+			Link::Collection(name) => write!(f, "@{}", name),
+		}
+	}
+}
+
+/// How a command refers to a bullet: by its position in the day, or by its persistent ID.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BulletRef {
+	Index(usize),
+	Id(String),
+}
+
+impl BulletRef {
+	pub fn matches(&self, b: &Bullet) -> bool {
+		match self {
+			BulletRef::Index(i) => b.visible_index == *i,
+			BulletRef::Id(id) => b.id.as_deref() == Some(id.as_str()),
+		}
+	}
+}
+
+impl From<usize> for BulletRef {
+	fn from(i: usize) -> Self { BulletRef::Index(i) }
+}
+
+impl FromStr for BulletRef {
+	type Err = anyhow::Error;
+
+	/// Plain numbers are positional indexes; anything else (with or without a leading `^`) is an ID.
+	fn from_str(s: &str) -> Result<Self> {
+		if let Ok(i) = s.parse::<usize>() {
+			return Ok(BulletRef::Index(i));
+		}
+		let id = s.strip_prefix('^').unwrap_or(s);
+		if !is_valid_id(id) { bail!("invalid bullet id: {}", s); }
+		Ok(BulletRef::Id(id.to_string()))
+	}
+}
+
+impl fmt::Display for BulletRef {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			BulletRef::Index(i) => write!(f, "{}", i),
+			BulletRef::Id(id) => write!(f, "^{}", id),
+		}
+	}
+}
+
+/// Whether `s` is an ID as [`new_id`] mints them: a letter, then letters
+/// and digits, [`ID_LEN`] long or [`LEGACY_ID_LEN`] for older journals.
+fn is_valid_id(s: &str) -> bool {
+	(s.len() == ID_LEN || s.len() == LEGACY_ID_LEN)
+		&& s.starts_with(|c: char| c.is_ascii_lowercase())
+		&& s.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+}
+
+/// Refuse bullet text ending in a `^word`, which could be read back as the
+/// bullet's ID. IDs are only ever assigned by the journal.
+pub(crate) fn check_text(text: &str) -> Result<()> {
+	match text.split_whitespace().last().and_then(|w| w.strip_prefix('^')) {
+		Some(word) if !word.is_empty() => bail!("text can't end with ^{} (IDs are assigned by bj)", word),
+		_ => Ok(()),
+	}
+}
+
+/// Text from outside the journal (e.g. a calendar title) made inert: markers
+/// that would be read back as a priority, meeting time, tags, links, series or
+/// ID get a look-alike character, and whitespace is collapsed, so the text
+/// parses back exactly as written.
+pub(crate) fn plain_text(text: &str) -> String {
+	let mut words: Vec<String> = text.split_whitespace().map(|w| {
+		let w = w.replace('→', "⟶").replace('←', "⟵").replace('↻', "⟳");
+		match w.strip_prefix('#') {
+			Some(rest) => format!("＃{}", rest),
+			None => w,
+		}
+	}).collect();
+	if let Some(first) = words.first_mut() {
+		if ["(!)", "(!!)", "(!!!)"].contains(&first.as_str()) { first.replace_range(..1, "（"); }
+		if first == "[mtg" { first.replace_range(..1, "［"); }
+	}
+	if let Some(last) = words.last_mut() {
+		if last.starts_with('^') { last.replace_range(..1, "ˆ"); }
+	}
+	words.join(" ")
+}
+
+/// Split a trailing ` ^id` marker off a bullet line.
+fn split_id(rest: &str) -> (&str, Option<String>) {
+	let trimmed = rest.trim_end();
+	if let Some(pos) = trimmed.rfind('^') {
+		let cand = &trimmed[pos + 1..];
+		let before = &trimmed[..pos];
+		if (before.is_empty() || before.ends_with(' ')) && is_valid_id(cand) {
+			return (before.trim_end(), Some(cand.to_string()));
+		}
+	}
+	(rest, None)
+}
+
+/// Length of newly minted IDs: long enough (about 2^41 values) that random
+/// IDs stay unique across a journal without scanning every page for them.
+const ID_LEN: usize = 8;
+
+/// Length of the IDs minted before they grew to [`ID_LEN`].
+const LEGACY_ID_LEN: usize = 4;
+
+/// Generate a random ID not present in `taken`, the IDs already on the page
+/// it is written to. IDs start with a letter so they can never be mistaken
+/// for a positional index.
+pub(crate) fn new_id(taken: &HashSet<String>) -> String {
+	loop {
+		let mut h = RandomState::new().build_hasher();
+		h.write_u128(SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_nanos()).unwrap_or_default());
+		let id = id_from(h.finish());
+		if !taken.contains(&id) { return id; }
+	}
+}
+
+/// ID of the occurrence of `series` on `date`. Derived rather than random,
+/// so an occurrence keeps its ID from being shown to being written out,
+/// and different on every day, so `^id` picks a single occurrence.
+pub(crate) fn occurrence_id(series: &str, date: NaiveDate) -> String {
+	// FNV-1a, which unlike the std hashers is the same in every build.
+	let mut n: u64 = 0xcbf2_9ce4_8422_2325;
+	for byte in format!("{}@{}", series, date).bytes() {
+		n = (n ^ u64::from(byte)).wrapping_mul(0x100_0000_01b3);
+	}
+	id_from(n)
+}
+
+/// Spell `n` as an ID: a letter, then letters and digits.
+fn id_from(mut n: u64) -> String {
+	const ALPHABET: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+	let mut id = String::new();
+	id.push((b'a' + (n % 26) as u8) as char);
+	n /= 26;
+	for _ in 1..ID_LEN {
+		id.push(ALPHABET[(n % 36) as usize] as char);
+		n /= 36;
+	}
+	id
+}
+
+/// Every `^id` on a page.
+pub(crate) fn ids_in(lines: &[String]) -> HashSet<String> {
+	parse_bullets(lines).into_iter().filter_map(|b| b.id).collect()
+}
+
+pub(crate) fn meeting_prefix(time: NaiveTime, duration_min: Option<u32>, tz: Option<Tz>) -> String {
+	format!("[mtg {}{}{}] ", time.format("%H:%M"), duration_min.map(|d| format!(" {}", d)).unwrap_or_default(), tz.map(|z| format!(" {}", z)).unwrap_or_default())
+}
+
+/// Wall-clock time `dt` in `tz`. Times skipped by a DST jump are read as
+/// if the clocks had not changed yet.
+pub(crate) fn zoned(dt: NaiveDateTime, tz: Tz) -> Option<DateTime<Tz>> {
+	tz.from_local_datetime(&dt).earliest().or_else(|| tz.from_local_datetime(&(dt + TimeDelta::hours(1))).earliest())
+}
+
+/// Wall-clock time `dt` in `tz` as local time.
+pub(crate) fn to_local(dt: NaiveDateTime, tz: Tz) -> NaiveDateTime {
+	zoned(dt, tz).map_or(dt, |z| z.with_timezone(&Local).naive_local())
+}
+
+/// Split the signifier off a bullet line. Checkbox bullets may be indented;
+/// plain `- ` notes must start at column 0 so `  - note:` lines stay notes.
+fn parse_marker(line: &str) -> Option<(Kind, State, &str)> {
+	let trimmed = line.trim_start();
+	if let Some(after) = trimmed.strip_prefix("- [") {
+		let mut chars = after.chars();
+		let marker = chars.next()?;
+		if let Some(rest) = chars.as_str().strip_prefix("] ") {
+			let parsed = match marker {
+				' ' => Some((Kind::Task, State::Open)),
+				'x' | 'X' => Some((Kind::Task, State::Done)),
+				'>' => Some((Kind::Task, State::Migrated)),
+				'<' => Some((Kind::Task, State::Scheduled)),
+				'-' | '~' => Some((Kind::Task, State::Cancelled)),
+				'o' => Some((Kind::Event, State::Open)),
+				_ => None,
+			};
+			if let Some((kind, state)) = parsed { return Some((kind, state, rest)); }
+		}
+	}
+	if line.len() == trimmed.len() {
+		if let Some(rest) = trimmed.strip_prefix("- ") {
+			return Some((Kind::Note, State::Open, rest));
+		}
+	}
+	None
+}
+
+pub(crate) fn marker(kind: Kind, state: State) -> &'static str {
+	match (kind, state) {
+		(Kind::Note, _) => "- ",
+		(Kind::Event, State::Open) => "- [o] ",
+		(_, State::Open) => "- [ ] ",
+		(_, State::Done) => "- [x] ",
+		(_, State::Migrated) => "- [>] ",
+		(_, State::Scheduled) => "- [<] ",
+		(_, State::Cancelled) => "- [-] ",
+	}
+}
+
+/// Parse every bullet (tasks, events and notes, with their note lines) out of a day file.
+pub fn parse_bullets(lines: &[String]) -> Vec<Bullet> {
+	let mut out = Vec::new();
+	let mut visible = 0usize;
+	for (idx, line) in lines.iter().enumerate() {
+		let Some((kind, state, rest)) = parse_marker(line) else { continue };
+		visible += 1;
+		let (rest, id) = split_id(rest);
+		let (rest, from, to) = split_links(rest);
+		let (rest, series) = split_series(rest);
+		let (migrated_from, migrated_from_month, migrated_from_collection) = Link::split(from);
+		let (migrated_to, migrated_to_month, migrated_to_collection) = Link::split(to);
+		let (text, pr, tags, mt, dur, tz) = parse_text_meeting_meta(rest);
+		let notes = collect_notes(lines, idx + 1);
+		out.push(Bullet { line_index: idx, visible_index: visible, id, kind, state, text, priority: pr, tags, notes, meeting_time: mt, meeting_duration_min: dur, meeting_tz: tz, recurring: series.is_some(), series, migrated_from, migrated_to, migrated_from_month, migrated_to_month, migrated_from_collection, migrated_to_collection });
+	}
+	out
+}
+
+/// Split trailing `← YYYY-MM-DD` / `→ YYYY-MM-DD` migration links (or
+/// `YYYY-MM` for a month page, `@name` for a collection) off a bullet line.
+fn split_links(rest: &str) -> (&str, Option<Link>, Option<Link>) {
+	let mut rest = rest.trim_end();
+	let mut to = None;
+	let mut from = None;
+	if let Some((head, d)) = split_link(rest, '→') { to = Some(d); rest = head; }
+	if let Some((head, d)) = split_link(rest, '←') { from = Some(d); rest = head; }
+	(rest, from, to)
+}
+
+fn split_link(s: &str, arrow: char) -> Option<(&str, Link)> {
+	let pos = s.rfind(arrow)?;
+	let target = s[pos + arrow.len_utf8()..].trim();
+	let link = match target.strip_prefix('@') {
+		Some(name) if !name.is_empty() => Link::Collection(name.to_string()),
+		Some(_) => return None,
+		None if target.len() == 7 => Link::Month(NaiveDate::parse_from_str(&format!("{}-01", target), "%Y-%m-%d").ok()?),
+		None => Link::Day(NaiveDate::parse_from_str(target, "%Y-%m-%d").ok()?),
+	};
+	Some((s[..pos].trim_end(), link))
+}
+
+/// Split a trailing `↻ id` series marker off a bullet line.
+fn split_series(rest: &str) -> (&str, Option<String>) {
+	let trimmed = rest.trim_end();
+	if let Some((before, id)) = trimmed.rsplit_once(" ↻ ") {
+		if is_valid_id(id) { return (before.trim_end(), Some(id.to_string())); }
+	}
+	(rest, None)
+}
+
+/// Turn a bullet line into a `[>]` (or `[<]`) marker pointing at `to`,
+/// keeping the rest of the line as written. Legacy lines without an ID get `id`.
+pub(crate) fn forwarded(line: &str, b: &Bullet, state: State, to: Option<&Link>, id: &str) -> String {
+	let line = with_state(line, b, state);
+	let arrow = to.map(|link| format!(" → {}", link)).unwrap_or_default();
+	let id_marker = format!(" ^{}", id);
+	match line.rfind(&id_marker).filter(|_| b.id.is_some()) {
+		Some(pos) => format!("{}{}{}", &line[..pos], arrow, &line[pos..]),
+		None => format!("{}{}{}", line.trim_end(), arrow, id_marker),
+	}
+}
+
+/// Rewrite the signifier of a bullet line, keeping indentation and content.
+pub(crate) fn with_state(line: &str, b: &Bullet, state: State) -> String {
+	let indent = &line[..line.len() - line.trim_start().len()];
+	let (_, _, rest) = parse_marker(line).expect("bullet line");
+	format!("{}{}{}", indent, marker(b.kind, state), rest)
+}
+
+fn collect_notes(lines: &[String], mut from: usize) -> Vec<String> {
+	let mut notes = Vec::new();
+	while from < lines.len() {
+		let l = &lines[from];
+		if let Some(n) = l.strip_prefix("  - note: ") {
+			notes.push(n.to_string());
+			from += 1;
+			continue;
+		}
+		break;
+	}
+	notes
+}
+
+/// Split priority marker and `#tags` off bullet text.
+pub fn parse_text_meta_only(rest: &str) -> (String, Option<u8>, Vec<String>) {
+	let mut text = rest.to_string();
+	let mut pr = None;
+	if let Some(stripped) = text.strip_prefix("(!!!) ") {
+		pr = Some(3);
+		text = stripped.to_string();
+	} else if let Some(stripped) = text.strip_prefix("(!!) ") {
+		pr = Some(2);
+		text = stripped.to_string();
+	} else if let Some(stripped) = text.strip_prefix("(!) ") {
+		pr = Some(1);
+		text = stripped.to_string();
+	}
+	let mut tags = Vec::new();
+	let parts: Vec<&str> = text.split_whitespace().collect();
+	let mut kept: Vec<&str> = Vec::new();
+	for p in parts {
+		if let Some(t) = p.strip_prefix('#') {
+			if !t.is_empty() { tags.push(t.to_string()); }
+		} else {
+			kept.push(p);
+		}
+	}
+	let final_text = kept.join(" ");
+	(final_text, pr, tags)
+}
+
+/// Like [`parse_text_meta_only`], but also understands a leading
+/// `[mtg HH:MM D ZONE]` marker, where the duration and IANA zone are optional.
+#[allow(clippy::type_complexity)]
+pub fn parse_text_meeting_meta(rest: &str) -> (String, Option<u8>, Vec<String>, Option<NaiveTime>, Option<u32>, Option<Tz>) {
+	let mut remaining = rest.to_string();
+	let mut meeting_time: Option<NaiveTime> = None;
+	let mut duration: Option<u32> = None;
+	let mut tz: Option<Tz> = None;
+	// Meeting prefix format: [mtg HH:MM], [mtg HH:MM D] or [mtg HH:MM D Europe/Berlin]
+	if let Some(body) = remaining.strip_prefix("[mtg ") {
+		if let Some(close_idx) = body.find(']') {
+			let spec = &body[..close_idx];
+			let after = &body[close_idx+1..];
+			let parts: Vec<&str> = spec.split_whitespace().collect();
+			if !parts.is_empty() {
+				if let Ok(t) = NaiveTime::parse_from_str(parts[0], "%H:%M") { meeting_time = Some(t); }
+				for p in &parts[1..] {
+					if let Ok(d) = p.parse::<u32>() { duration = Some(d); } else if let Ok(z) = p.parse::<Tz>() { tz = Some(z); }
+				}
+			}
+			remaining = after.trim_start().to_string();
+		}
+	}
+	let (text, pr, tags) = parse_text_meta_only(&remaining);
+	(text, pr, tags, meeting_time, duration, tz)
+}
+
+fn priority_prefix(priority: Option<u8>) -> &'static str {
+	match priority {
+		Some(3) => "(!!!) ",
+		Some(2) => "(!!) ",
+		Some(1) => "(!) ",
+		_ => "",
+	}
+}
+
+/// Render an open bullet line in the on-disk format.
+pub(crate) fn format_bullet_line(kind: Kind, text: &str, priority: Option<u8>, tags: &[String], id: &str) -> String {
+	let mut suffix = String::new();
+	for t in tags { suffix.push_str(&format!(" #{}", t)); }
+	format!("{}{}{}{} ^{}", marker(kind, State::Open), priority_prefix(priority), text.trim(), suffix, id)
+}
+
+/// A `YYYY-MM-DD` date as stored in journal files.
+pub(crate) fn parse_iso_date(s: &str) -> Result<NaiveDate> {
+	NaiveDate::parse_from_str(s, "%Y-%m-%d").with_context(|| format!("invalid date: {}", s))
+}
+
+/// Parse `low`/`med`/`high` (or `1`/`2`/`3`) into a priority level.
+pub fn parse_priority_opt(v: Option<&str>) -> Result<Option<u8>> {
+	match v {
+		None => Ok(None),
+		Some(s) => {
+			let s = s.to_lowercase();
+			let p = match s.as_str() {
+				"3" | "high" | "h" => 3,
+				"2" | "med" | "m" | "medium" => 2,
+				"1" | "low" | "l" => 1,
+				_ => bail!("invalid priority: {}", s),
+			};
+			Ok(Some(p))
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_text_meta_only() {
+        // Test priority and tags
+        let s = "(!!!) Test bullet #work #urgent";
+        let (text, pr, tags) = parse_text_meta_only(s);
+        assert_eq!(text, "Test bullet", "Text not correctly extracted");
+        assert_eq!(pr, Some(3), "High priority not detected");
+        assert_eq!(tags, vec!["work".to_string(), "urgent".to_string()], "Tags not correctly parsed");
+
+        // Test medium priority
+        let s = "(!!) Medium priority #dev";
+        let (text, pr, tags) = parse_text_meta_only(s);
+        assert_eq!(text, "Medium priority", "Text with medium priority not extracted");
+        assert_eq!(pr, Some(2), "Medium priority not detected");
+        assert_eq!(tags, vec!["dev".to_string()], "Single tag not parsed");
+
+        // Test no metadata
+        let s = "Simple bullet";
+        let (text, pr, tags) = parse_text_meta_only(s);
+        assert_eq!(text, "Simple bullet", "Plain text not preserved");
+        assert_eq!(pr, None, "Should have no priority");
+        assert!(tags.is_empty(), "Should have no tags");
+    }
+
+    #[test]
+    fn test_parse_text_meeting_meta() {
+        // Test full meeting metadata
+        let s = "[mtg 15:30 45] Team sync #work";
+        let (text, pr, tags, mt, dur, tz) = parse_text_meeting_meta(s);
+        assert_eq!(text, "Team sync", "Meeting text not extracted");
+        assert_eq!(pr, None, "Should have no priority");
+        assert_eq!(tags, vec!["work".to_string()], "Meeting tag not parsed");
+        assert_eq!(mt.unwrap().format("%H:%M").to_string(), "15:30", "Meeting time not parsed");
+        assert_eq!(dur, Some(45), "Meeting duration not parsed");
+        assert_eq!(tz, None, "Should have no zone");
+
+        // Test meeting with a zone
+        let s = "[mtg 15:00 30 Europe/Berlin] Planning";
+        let (text, _pr, _tags, mt, dur, tz) = parse_text_meeting_meta(s);
+        assert_eq!((text.as_str(), mt, dur), ("Planning", NaiveTime::from_hms_opt(15, 0, 0), Some(30)), "Zoned meeting not parsed");
+        assert_eq!(tz, Some(chrono_tz::Europe::Berlin), "Meeting zone not parsed");
+        assert_eq!(meeting_prefix(mt.unwrap(), dur, tz), "[mtg 15:00 30 Europe/Berlin] ", "Zone should round-trip");
+
+        // Test meeting without duration
+        let s = "[mtg 09:00] Daily standup";
+        let (text, _pr, _tags, mt, dur, _tz) = parse_text_meeting_meta(s);
+        assert_eq!(text, "Daily standup", "Simple meeting text not extracted");
+        assert_eq!(mt.unwrap().format("%H:%M").to_string(), "09:00", "Simple meeting time not parsed");
+        assert_eq!(dur, None, "Should have no duration");
+
+        // Test non-meeting text
+        let s = "Regular bullet";
+        let (text, _pr, _tags, mt, dur, _tz) = parse_text_meeting_meta(s);
+        assert_eq!(text, "Regular bullet", "Non-meeting text should be preserved");
+        assert!(mt.is_none(), "Non-meeting should have no time");
+        assert!(dur.is_none(), "Non-meeting should have no duration");
+    }
+
+    #[test]
+    fn test_meeting_start_in_zone() {
+        use chrono_tz::Europe::Berlin;
+        let date = NaiveDate::from_ymd_opt(2025, 11, 6).unwrap();
+        let local = |tz: Tz, dt: NaiveDateTime| tz.from_local_datetime(&dt).unwrap().with_timezone(&Local).naive_local();
+
+        let zoned = parse_bullets(&["- [ ] [mtg 15:00 30 Europe/Berlin] Planning ^k3x9".to_string()]).remove(0);
+        assert_eq!(zoned.meeting_start(date), Some(local(Berlin, date.and_hms_opt(15, 0, 0).unwrap())), "Zoned meetings should start in local time");
+        let floating = parse_bullets(&["- [ ] [mtg 15:00 30] Planning ^k3x9".to_string()]).remove(0);
+        assert_eq!(floating.meeting_start(date), Some(date.and_hms_opt(15, 0, 0).unwrap()), "Meetings without a zone are already local");
+
+        // 02:30 does not exist in Berlin on 2025-03-30; it is read as 02:30 CET (01:30 UTC).
+        let gap = NaiveDate::from_ymd_opt(2025, 3, 30).unwrap().and_hms_opt(2, 30, 0).unwrap();
+        assert_eq!(to_local(gap, Berlin), local(chrono_tz::UTC, NaiveDate::from_ymd_opt(2025, 3, 30).unwrap().and_hms_opt(1, 30, 0).unwrap()), "DST gap mismatch");
+    }
+
+    #[test]
+    fn test_parse_bullet_id() {
+        let lines = vec![
+            "- [ ] (!) Call back #home ^k3x9".to_string(),
+            "- [x] Legacy bullet".to_string(),
+            "- [ ] Price is 2^10".to_string(),
+        ];
+        let bullets = parse_bullets(&lines);
+        assert_eq!(bullets[0].id.as_deref(), Some("k3x9"), "ID not parsed");
+        assert_eq!(bullets[0].text, "Call back", "ID should not leak into text");
+        assert_eq!(bullets[0].tags, vec!["home"], "Tags before the ID should still parse");
+        assert_eq!(bullets[1].id, None, "Legacy bullets have no ID");
+        assert_eq!(bullets[2].id, None, "Caret inside a word is not an ID");
+        assert_eq!(bullets[2].text, "Price is 2^10", "Text with caret should be preserved");
+
+        let odd = vec!["- [ ] Try ^beta ^9abcdefg ^abc".to_string()];
+        assert_eq!(parse_bullets(&odd)[0].id, None, "Only IDs of the minted form are markers");
+        assert!(check_text("bump to ^v2beta01").is_err(), "A trailing ^word could pass for an ID");
+        assert!(check_text("2^10 and ^beta later").is_ok());
+    }
+
+    #[test]
+    fn test_parse_signifiers() {
+        let lines: Vec<String> = [
+            "- [ ] Open task",
+            "- [x] Done task",
+            "- [>] Migrated task",
+            "- [<] Scheduled task",
+            "- [-] Cancelled task",
+            "- [~] Also cancelled",
+            "- [o] Launch party",
+            "- Idea for later",
+            "  - note: not a bullet",
+            "- [link](https://example.com)",
+        ].iter().map(|s| s.to_string()).collect();
+        let b = parse_bullets(&lines);
+        let got: Vec<(Kind, State)> = b.iter().map(|b| (b.kind, b.state)).collect();
+        assert_eq!(got, vec![
+            (Kind::Task, State::Open),
+            (Kind::Task, State::Done),
+            (Kind::Task, State::Migrated),
+            (Kind::Task, State::Scheduled),
+            (Kind::Task, State::Cancelled),
+            (Kind::Task, State::Cancelled),
+            (Kind::Event, State::Open),
+            (Kind::Note, State::Open),
+            (Kind::Note, State::Open),
+        ], "Signifiers not parsed");
+        assert_eq!(b[7].text, "Idea for later", "Note text mismatch");
+        assert_eq!(b[8].text, "[link](https://example.com)", "Unknown checkbox should fall back to a note");
+        assert_eq!(with_state(&lines[0], &b[0], State::Cancelled), "- [-] Open task", "State rewrite mismatch");
+    }
+
+    #[test]
+    fn test_migration_links_round_trip() {
+        let lines = vec!["- [>] (!) Ship it #work ← 2025-11-03 → 2025-11-10 ^k3x9".to_string()];
+        let b = parse_bullets(&lines).remove(0);
+        assert_eq!(b.text, "Ship it", "Links should not leak into text");
+        assert_eq!(b.migrated_from, NaiveDate::from_ymd_opt(2025, 11, 3), "Source link not parsed");
+        assert_eq!(b.migrated_to, NaiveDate::from_ymd_opt(2025, 11, 10), "Destination link not parsed");
+        assert_eq!(b.to_line(), lines[0], "Line should round-trip");
+
+        let open = vec!["- [ ] Ship it #work ^k3x9".to_string()];
+        let ob = parse_bullets(&open).remove(0);
+        let fwd = forwarded(&open[0], &ob, State::Migrated, Some(&Link::Day(NaiveDate::from_ymd_opt(2025, 11, 10).unwrap())), "k3x9");
+        assert_eq!(fwd, "- [>] Ship it #work → 2025-11-10 ^k3x9", "Forwarding marker should sit before the ID");
+
+        let parked = vec!["- [>] Ship it ← @Reading list → @Ideas ^k3x9".to_string()];
+        let pb = parse_bullets(&parked).remove(0);
+        assert_eq!((pb.text.as_str(), pb.migrated_from_collection.as_deref(), pb.migrated_to_collection.as_deref()),
+                   ("Ship it", Some("Reading list"), Some("Ideas")), "Collection links not parsed");
+        assert_eq!(pb.to_line(), parked[0], "Collection links should round-trip");
+
+        let planned = vec!["- [ ] Renew passport ← 2026-03 ^k3x9m2qa".to_string()];
+        let mb = parse_bullets(&planned).remove(0);
+        assert_eq!((mb.text.as_str(), mb.migrated_from_month), ("Renew passport", NaiveDate::from_ymd_opt(2026, 3, 1)), "Month link not parsed");
+        assert_eq!(mb.to_line(), planned[0], "Month links should round-trip");
+
+        let occurrence = vec!["- [>] Stretch ↻ k3x9m2qa → 2025-11-10 ^p7q2w9xz".to_string()];
+        let ob = parse_bullets(&occurrence).remove(0);
+        assert_eq!((ob.text.as_str(), ob.series.as_deref(), ob.recurring), ("Stretch", Some("k3x9m2qa"), true), "Series marker not parsed");
+        assert_eq!(ob.to_line(), occurrence[0], "Series marker should round-trip");
+    }
+
+    #[test]
+    fn test_bullet_ref_from_str() {
+        assert_eq!("3".parse::<BulletRef>().unwrap(), BulletRef::Index(3));
+        assert_eq!("^k3x9".parse::<BulletRef>().unwrap(), BulletRef::Id("k3x9".to_string()));
+        assert_eq!("k3x9".parse::<BulletRef>().unwrap(), BulletRef::Id("k3x9".to_string()));
+        assert!("^K3!".parse::<BulletRef>().is_err(), "Invalid IDs should be rejected");
+    }
+
+    #[test]
+    fn test_new_id_avoids_taken() {
+        let taken: HashSet<String> = (0..50).map(|_| new_id(&HashSet::new())).collect();
+        let id = new_id(&taken);
+        assert!(!taken.contains(&id), "Fresh IDs skip the page's own");
+        assert_eq!(id.len(), ID_LEN);
+        assert!(id.starts_with(|c: char| c.is_ascii_lowercase()), "IDs never look like positions");
+        assert_eq!(format!("^{}", id).parse::<BulletRef>().unwrap(), BulletRef::Id(id));
+    }
+
+    #[test]
+    fn test_bullet_matches_filters() {
+        let lines = vec!["- [ ] (!!) Ship it #work #release".to_string()];
+        let b = &parse_bullets(&lines)[0];
+        assert!(b.matches(&[], None), "No filters should match");
+        assert!(b.matches(&["work".to_string()], Some(2)), "Tag and priority should match");
+        assert!(!b.matches(&["home".to_string()], None), "Missing tag should not match");
+        assert!(!b.matches(&[], Some(3)), "Different priority should not match");
+    }
+}
@@ -0,0 +1,67 @@
+//! The current time, as the journal sees it. Everything that depends on
+//! "now" or "today" asks a [`Clock`], so a fixed one makes it reproducible.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+
+use crate::date::parse_date;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Clock {
+	fixed: Option<NaiveDateTime>,
+}
+
+impl Clock {
+	/// The local wall clock.
+	pub fn system() -> Self {
+		Clock { fixed: None }
+	}
+
+	/// A clock stopped at `now` (local time).
+	pub fn fixed(now: NaiveDateTime) -> Self {
+		Clock { fixed: Some(now) }
+	}
+
+	/// Parse `YYYY-MM-DDTHH:MM[:SS]`, `YYYY-MM-DD HH:MM[:SS]`, or a date
+	/// expression (taken at midnight), into a fixed clock.
+	pub fn parse(s: &str) -> Result<Self> {
+		let s = s.trim();
+		for format in ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"] {
+			if let Ok(now) = NaiveDateTime::parse_from_str(s, format) { return Ok(Self::fixed(now)); }
+		}
+		let date = parse_date(s).with_context(|| format!("invalid time: {} (expected YYYY-MM-DDTHH:MM or a date)", s))?;
+		Ok(Self::fixed(date.and_time(NaiveTime::MIN)))
+	}
+
+	pub fn now(&self) -> NaiveDateTime {
+		self.fixed.unwrap_or_else(|| Local::now().naive_local())
+	}
+
+	/// [`Clock::now`] as a UTC instant, for timestamps written to other tools.
+	pub fn now_utc(&self) -> DateTime<Utc> {
+		match self.fixed {
+			Some(now) => Local.from_local_datetime(&now).earliest().map_or_else(|| now.and_utc(), |t| t.with_timezone(&Utc)),
+			None => Utc::now(),
+		}
+	}
+
+	pub fn today(&self) -> NaiveDate {
+		self.now().date()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_fixed_clock() -> Result<()> {
+        let at = |s: &str| NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").unwrap();
+        assert_eq!(Clock::parse("2025-11-19T09:30")?.now(), at("2025-11-19 09:30:00"));
+        assert_eq!(Clock::parse("2025-11-19 09:30:15")?.now(), at("2025-11-19 09:30:15"));
+        assert_eq!(Clock::parse("2025-11-19")?.now(), at("2025-11-19 00:00:00"), "A date means midnight");
+        assert_eq!(Clock::parse("2025-11-19")?.today(), NaiveDate::from_ymd_opt(2025, 11, 19).unwrap());
+        assert!(Clock::parse("half past nine").is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,175 @@
+//! Named collections: pages such as "Reading list" or "Ideas" that are not
+//! tied to a date, stored as `collections/NAME.md` under the journal root.
+//! They hold ordinary bullets, and bullets migrate between a collection and
+//! a day in either direction.
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use chrono::NaiveDate;
+
+use crate::bullet::{parse_bullets, with_state, Bullet, BulletRef, Kind, Link, State};
+use crate::journal::{read_file_lines, Journal};
+
+/// Subfolder of the journal root holding the collections.
+pub(crate) const COLLECTIONS_DIR: &str = "collections";
+
+fn check_name(name: &str) -> Result<()> {
+	if name.trim().is_empty() || name != name.trim() { bail!("invalid collection name: {:?}", name); }
+	if name.starts_with('.') || name.contains(['/', '\\', '^', '←', '→']) || name.chars().any(char::is_control) {
+		bail!("invalid collection name: {} (no slashes, arrows, ^ or leading dot)", name);
+	}
+	Ok(())
+}
+
+impl Journal {
+	pub fn file_for_collection(&self, name: &str) -> PathBuf {
+		self.root().join(COLLECTIONS_DIR).join(format!("{}.md", name))
+	}
+
+	/// Names of every collection, sorted.
+	pub fn collections(&self) -> Result<Vec<String>> {
+		let dir = self.root().join(COLLECTIONS_DIR);
+		if !dir.exists() { return Ok(Vec::new()); }
+		let mut names = Vec::new();
+		for entry in fs::read_dir(&dir).with_context(|| format!("read {}", dir.display()))? {
+			let name = entry?.file_name();
+			let Some(stem) = name.to_str().and_then(|n| n.strip_suffix(".md")) else { continue };
+			if check_name(stem).is_ok() { names.push(stem.to_string()); }
+		}
+		names.sort();
+		Ok(names)
+	}
+
+	/// Start an empty collection, headed by its name.
+	pub fn new_collection(&self, name: &str) -> Result<PathBuf> {
+		check_name(name)?;
+		let path = self.file_for_collection(name);
+		if path.exists() { bail!("collection {} already exists", name); }
+		fs::create_dir_all(path.parent().expect("collections dir"))?;
+		self.operation("collection new", || self.write_lines(&path, &[format!("# {}", name)]))?;
+		Ok(path)
+	}
+
+	/// Path of an existing collection.
+	fn collection_path(&self, name: &str) -> Result<PathBuf> {
+		check_name(name)?;
+		let path = self.file_for_collection(name);
+		if !path.exists() { bail!("no collection named {} (create it with `bj collection new`)", name); }
+		Ok(path)
+	}
+
+	/// Bullets in a collection, in file order.
+	pub fn collection_bullets(&self, name: &str) -> Result<Vec<Bullet>> {
+		Ok(parse_bullets(&read_file_lines(&self.collection_path(name)?)?))
+	}
+
+	/// Append a task, event or note to a collection under a fresh ID and
+	/// return it.
+	pub fn add_to_collection(&self, name: &str, kind: Kind, text: &str, priority: Option<u8>, tags: &[String], notes: &[String]) -> Result<Bullet> {
+		self.add_to(&self.collection_path(name)?, kind, text, priority, tags, notes)
+	}
+
+	/// Mark task `id` in a collection done and return it.
+	pub fn collection_done(&self, name: &str, id: impl Into<BulletRef>) -> Result<Bullet> {
+		let path = self.collection_path(name)?;
+		self.operation("done", || {
+			let id = id.into();
+			let mut lines = read_file_lines(&path)?;
+			let Some(target) = parse_bullets(&lines).into_iter().find(|b| id.matches(b)) else { bail!("bullet {} not found in {}", id, name) };
+			if target.kind != Kind::Task { bail!("bullet {} is not a task", id); }
+			if target.state != State::Open && target.state != State::Done { bail!("bullet {} is not open", id); }
+			lines[target.line_index] = with_state(&lines[target.line_index], &target, State::Done);
+			self.write_lines(&path, &lines)?;
+			Ok(Bullet { state: State::Done, ..target })
+		})
+	}
+
+	/// Move open task `id` from `date` into a collection, leaving a `[>]`
+	/// marker pointing at `@name` on the day, and return it.
+	pub fn migrate_to_collection(&self, date: NaiveDate, id: impl Into<BulletRef>, name: &str) -> Result<Bullet> {
+		let to = self.collection_path(name)?;
+		self.operation("migrate", || {
+			let id = id.into();
+			self.materialize(date)?;
+			let path = self.file_for(date);
+			let mut lines = read_file_lines(&path)?;
+			let Some(target) = parse_bullets(&lines).into_iter().find(|b| id.matches(b)) else { bail!("bullet {} not found on {}", id, date) };
+			if !target.is_open() { bail!("bullet {} is not an open task", id); }
+			self.forward(Link::Day(date), &mut lines, &target, &to, Link::Collection(name.to_string()), State::Migrated)?;
+			self.write_lines(&path, &lines)?;
+			self.suppress_occurrence(date, &target)?;
+			Ok(target)
+		})
+	}
+
+	/// Move open task `id` from a collection into the daily log of `to`,
+	/// leaving a `[>]` marker in the collection, and return it. The copy on
+	/// the day points back at `@name`.
+	pub fn migrate_from_collection(&self, name: &str, id: impl Into<BulletRef>, to: NaiveDate) -> Result<Bullet> {
+		let path = self.collection_path(name)?;
+		self.operation("migrate", || {
+			let id = id.into();
+			let mut lines = read_file_lines(&path)?;
+			let Some(target) = parse_bullets(&lines).into_iter().find(|b| id.matches(b)) else { bail!("bullet {} not found in {}", id, name) };
+			if !target.is_open() { bail!("bullet {} is not an open task", id); }
+			self.forward(Link::Collection(name.to_string()), &mut lines, &target, &self.file_for(to), Link::Day(to), State::Migrated)?;
+			self.write_lines(&path, &lines)?;
+			Ok(target)
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::Page;
+    use crate::test_support::TestEnv;
+
+    #[test]
+    fn test_collections_hold_bullets_and_migrate_both_ways() -> Result<()> {
+        let env = TestEnv::new();
+        let j = &env.journal;
+        let day = NaiveDate::from_ymd_opt(2025, 11, 6).unwrap();
+        j.new_collection("Reading list")?;
+        j.new_collection("Ideas")?;
+        assert!(j.new_collection("Ideas").is_err(), "Names are unique");
+        assert!(j.new_collection("../escape").is_err());
+        assert!(j.new_collection("A → B").is_err(), "Arrows would read as links");
+        assert_eq!(j.collections()?, vec!["Ideas", "Reading list"]);
+        assert!(j.add_to_collection("Nope", Kind::Task, "x", None, &[], &[]).is_err(), "Collections must exist");
+
+        let book = j.add_to_collection("Reading list", Kind::Task, "Dune", Some(2), &["scifi".into()], &["library copy".into()])?;
+        let bullets = j.collection_bullets("Reading list")?;
+        assert_eq!((bullets[0].text.as_str(), bullets[0].priority, bullets[0].tags.clone(), bullets[0].notes.clone()),
+                   ("Dune", Some(2), vec!["scifi".to_string()], vec!["library copy".to_string()]), "Same bullet format as days");
+
+        let book_id = BulletRef::Id(book.id.clone().unwrap());
+        j.migrate_from_collection("Reading list", book_id.clone(), day)?;
+        let moved = &j.bullets(day)?[0];
+        assert_eq!((moved.text.as_str(), moved.id.clone(), moved.notes.len()), ("Dune", book.id.clone(), 1));
+        assert_eq!(moved.migrated_from_collection.as_deref(), Some("Reading list"), "The day points back at the collection");
+        let left = &j.collection_bullets("Reading list")?[0];
+        assert_eq!((left.state, left.migrated_to), (State::Migrated, Some(day)));
+
+        let idea = j.add_bullet(day, "Write a blog engine", None, &[], &[])?;
+        j.migrate_to_collection(day, BulletRef::Id(idea.id.clone().unwrap()), "Ideas")?;
+        let parked = &j.collection_bullets("Ideas")?[0];
+        assert_eq!((parked.text.as_str(), parked.migrated_from), ("Write a blog engine", Some(day)));
+        let marker = &j.bullets(day)?[1];
+        assert_eq!((marker.state, marker.migrated_to_collection.as_deref()), (State::Migrated, Some("Ideas")));
+        let chain: Vec<_> = j.history(idea.id.as_deref().unwrap())?.into_iter().map(|h| h.page).collect();
+        assert_eq!(chain, vec![Page::Day, Page::Collection("Ideas".to_string())], "History follows the bullet into the collection");
+
+        j.collection_done("Ideas", BulletRef::Id(idea.id.unwrap()))?;
+        assert!(j.collection_bullets("Ideas")?[0].is_done());
+        assert!(j.collection_done("Reading list", book_id).is_err(), "Migrated bullets are not open");
+
+        j.undo()?;
+        j.undo()?;
+        assert!(j.collection_bullets("Ideas")?.is_empty(), "Undo covers collection files");
+        assert!(j.bullets(day)?[1].is_open());
+        Ok(())
+    }
+}
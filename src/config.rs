@@ -0,0 +1,266 @@
+//! User configuration, read from `config.toml` in the platform config dir
+//! (`~/.config/bullet_journal/config.toml` on Linux). Every key is optional:
+//!
+//! ```toml
+//! default = "work"
+//!
+//! [journals]
+//! work = "~/notes/work"
+//! personal = "~/notes/personal"
+//!
+//! [defaults]
+//! priority = "med"
+//! tags = ["work"]
+//! meeting_minutes = 30
+//! notify_window = 10
+//!
+//! [display]
+//! week_start = "sunday"
+//! glyphs = "ascii"
+//! date_format = "%d.%m.%Y"
+//! heading_format = "%A %d %B"
+//! day_format = "%a %d %b"
+//! box_width = 60
+//!
+//! [colors]
+//! accent = "bright blue"
+//! done = "cyan"
+//! ```
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use chrono::format::{Item, StrftimeItems};
+use chrono::Weekday;
+use directories::{BaseDirs, ProjectDirs};
+use serde::Deserialize;
+
+use crate::bullet::parse_priority_opt;
+use crate::journal::default_dir;
+use crate::recur::parse_weekday;
+
+/// Name of a project journal directory, found by walking up from the
+/// working directory.
+pub const LOCAL_DIR: &str = ".bj";
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+	/// Journal used when nothing else selects one.
+	pub default: Option<String>,
+	/// Named journals and their directories; `~/` is expanded.
+	#[serde(default)]
+	pub journals: BTreeMap<String, PathBuf>,
+	#[serde(default)]
+	pub defaults: Defaults,
+	#[serde(default)]
+	pub display: Display,
+	/// Colour name (`red`, `bright blue`, ...) per entry of [`COLOR_ROLES`].
+	#[serde(default)]
+	pub colors: BTreeMap<String, String>,
+}
+
+/// Values used when a command doesn't give them.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Defaults {
+	/// Priority of bullets added without `-p`.
+	pub priority: Option<String>,
+	/// Tags added to every bullet created from the command line or the TUI.
+	#[serde(default)]
+	pub tags: Vec<String>,
+	/// Duration written by `bj meeting add` without `-u`.
+	pub meeting_minutes: Option<u32>,
+	/// Look-ahead of `bj meeting notify` without `-w`.
+	pub notify_window: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Display {
+	/// First day of the week in week and month views.
+	pub week_start: Option<String>,
+	#[serde(default)]
+	pub glyphs: Glyphs,
+	/// `false` turns colour off.
+	pub color: Option<bool>,
+	/// strftime format for dates in rows and messages.
+	pub date_format: Option<String>,
+	/// strftime format for the day heading of `bj list` and the TUI.
+	pub heading_format: Option<String>,
+	/// strftime format for day headings in week views.
+	pub day_format: Option<String>,
+	/// Width of the `bj list` header box and, where wider than the grid, the `bj cal` frame.
+	pub box_width: Option<usize>,
+}
+
+/// Symbol set for signifiers, priorities and decorations.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Glyphs {
+	#[default]
+	Unicode,
+	Ascii,
+	Emoji,
+}
+
+/// What each `[colors]` key paints.
+pub const COLOR_ROLES: [&str; 13] = ["accent", "title", "tag", "muted", "done", "migrated", "cancelled", "event", "high", "medium", "low", "warning", "weekend"];
+
+/// Narrowest `box_width` that still fits the header's progress line.
+const MIN_BOX_WIDTH: usize = 30;
+
+/// Where the selected journal came from, for `bj journals`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+	Flag,
+	Env,
+	Local,
+	Default,
+	Platform,
+}
+
+impl Config {
+	/// Path of the config file, whether or not it exists.
+	pub fn path() -> Result<PathBuf> {
+		let proj = ProjectDirs::from("dev", "local", "bullet_journal").context("cannot resolve project dirs")?;
+		Ok(proj.config_dir().join("config.toml"))
+	}
+
+	/// Load the config file, or the defaults if there is none.
+	pub fn load() -> Result<Self> {
+		let path = Self::path()?;
+		if !path.exists() { return Ok(Self::default()); }
+		let text = fs::read_to_string(&path).with_context(|| format!("read {}", path.display()))?;
+		Self::parse(&text).with_context(|| format!("parse {}", path.display()))
+	}
+
+	pub fn parse(text: &str) -> Result<Self> {
+		let config: Config = toml::from_str(text)?;
+		config.default_priority()?;
+		config.week_start()?;
+		for (role, name) in &config.colors {
+			if !COLOR_ROLES.contains(&role.as_str()) { bail!("unknown colour role: {} (expected one of {})", role, COLOR_ROLES.join(", ")); }
+			if name.parse::<colored::Color>().is_err() { bail!("unknown colour for {}: {}", role, name); }
+		}
+		for format in [&config.display.date_format, &config.display.heading_format, &config.display.day_format].into_iter().flatten() {
+			if StrftimeItems::new(format).any(|i| matches!(i, Item::Error)) { bail!("invalid date format: {}", format); }
+		}
+		if let Some(w) = config.display.box_width.filter(|w| *w < MIN_BOX_WIDTH) { bail!("box_width {} is too narrow (at least {})", w, MIN_BOX_WIDTH); }
+		Ok(config)
+	}
+
+	pub fn default_priority(&self) -> Result<Option<u8>> {
+		parse_priority_opt(self.defaults.priority.as_deref())
+	}
+
+	/// `tags` followed by any default tags they don't already have.
+	pub fn with_default_tags(&self, tags: &[String]) -> Vec<String> {
+		let mut all = tags.to_vec();
+		for t in &self.defaults.tags {
+			if !all.contains(t) { all.push(t.clone()); }
+		}
+		all
+	}
+
+	pub fn week_start(&self) -> Result<Weekday> {
+		match &self.display.week_start {
+			None => Ok(Weekday::Mon),
+			Some(s) => parse_weekday(s).with_context(|| format!("invalid week_start: {}", s)),
+		}
+	}
+
+	/// Colour configured for `role`, if any.
+	pub fn color(&self, role: &str) -> Option<colored::Color> {
+		self.colors.get(role)?.parse().ok()
+	}
+
+	/// Directory of a configured journal name, or of `name` taken as a path
+	/// when it looks like one (contains `/`, or starts with `~` or `.`).
+	/// Any other name must be configured, so a typo can't open a new journal.
+	pub fn journal_path(&self, name: &str) -> Result<PathBuf> {
+		if let Some(path) = self.journals.get(name) { return Ok(expand_home(path)); }
+		if name.contains('/') || name.starts_with(['~', '.']) { return Ok(expand_home(Path::new(name))); }
+		let known: Vec<&str> = self.journals.keys().map(String::as_str).collect();
+		if known.is_empty() { bail!("no journal named {} (none configured; use a path like ./{} for a directory)", name, name); }
+		bail!("no journal named {} (configured: {})", name, known.join(", "))
+	}
+
+	/// Pick the journal directory: `--journal` (a name or a path), then
+	/// `BJ_DIR`, then a `.bj/` in `cwd` or above, then the configured
+	/// default, then the platform data dir.
+	pub fn select(&self, flag: Option<&str>, env_dir: Option<&str>, cwd: &Path) -> Result<(PathBuf, Source)> {
+		if let Some(name) = flag { return Ok((self.journal_path(name)?, Source::Flag)); }
+		if let Some(dir) = env_dir.filter(|d| !d.is_empty()) { return Ok((expand_home(Path::new(dir)), Source::Env)); }
+		if let Some(dir) = cwd.ancestors().map(|d| d.join(LOCAL_DIR)).find(|d| d.is_dir()) { return Ok((dir, Source::Local)); }
+		if let Some(name) = &self.default { return Ok((self.journal_path(name)?, Source::Default)); }
+		Ok((default_dir()?, Source::Platform))
+	}
+
+	/// [`Config::select`] with the process environment and working directory.
+	pub fn select_current(&self, flag: Option<&str>) -> Result<(PathBuf, Source)> {
+		let cwd = env::current_dir().context("cannot read the working directory")?;
+		self.select(flag, env::var("BJ_DIR").ok().as_deref(), &cwd)
+	}
+}
+
+fn expand_home(path: &Path) -> PathBuf {
+	match (path.strip_prefix("~"), BaseDirs::new()) {
+		(Ok(rest), Some(dirs)) => dirs.home_dir().join(rest),
+		_ => path.to_path_buf(),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TestEnv;
+
+    #[test]
+    fn test_select_order() -> Result<()> {
+        let env = TestEnv::new();
+        let config = Config::parse("default = \"work\"\n[journals]\nwork = \"/srv/work\"\npersonal = \"/srv/personal\"\n")?;
+        let project = env.journal.root().join("project");
+        let nested = project.join("src");
+        fs::create_dir_all(&nested)?;
+
+        assert_eq!(config.select(Some("personal"), Some("/tmp/env"), &nested)?, (PathBuf::from("/srv/personal"), Source::Flag));
+        assert_eq!(config.select(Some("/tmp/other"), None, &nested)?.0, PathBuf::from("/tmp/other"), "Paths are taken as given");
+        assert_eq!(config.select(Some("./side"), None, &nested)?.0, PathBuf::from("./side"));
+        let typo = config.select(Some("wrok"), None, &nested).unwrap_err().to_string();
+        assert!(typo.contains("personal, work"), "Unknown names are rejected with the configured ones: {}", typo);
+        assert_eq!(config.select(None, Some("/tmp/env"), &nested)?, (PathBuf::from("/tmp/env"), Source::Env));
+        assert_eq!(config.select(None, None, &nested)?, (PathBuf::from("/srv/work"), Source::Default));
+
+        fs::create_dir(project.join(LOCAL_DIR))?;
+        assert_eq!(config.select(None, None, &nested)?, (project.join(LOCAL_DIR), Source::Local), "A .bj/ above the working dir wins over the default");
+        assert_eq!(Config::default().select(None, None, env.journal.root())?.1, Source::Platform);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_keys() {
+        assert!(Config::parse("defualt = \"work\"").is_err());
+        assert!(Config::parse("").is_ok());
+    }
+
+    #[test]
+    fn test_parse_display_and_defaults() -> Result<()> {
+        let config = Config::parse("[defaults]\npriority = \"high\"\ntags = [\"work\"]\n[display]\nweek_start = \"sun\"\nglyphs = \"ascii\"\n[colors]\ndone = \"bright blue\"\n")?;
+        assert_eq!(config.default_priority()?, Some(3));
+        assert_eq!(config.with_default_tags(&["home".into(), "work".into()]), ["home", "work"], "Default tags are not repeated");
+        assert_eq!(config.week_start()?, Weekday::Sun);
+        assert_eq!(config.display.glyphs, Glyphs::Ascii);
+        assert_eq!(config.color("done"), Some(colored::Color::BrightBlue));
+        assert_eq!(Config::default().week_start()?, Weekday::Mon, "Weeks start on Monday by default");
+
+        for bad in ["[defaults]\npriority = \"urgent\"", "[display]\nweek_start = \"someday\"", "[display]\nglyphs = \"runes\"",
+                    "[colors]\ndone = \"plaid\"", "[colors]\nbackground = \"red\"", "[display]\ndate_format = \"%Q\"", "[display]\nbox_width = 10"] {
+            assert!(Config::parse(bad).is_err(), "Should reject {:?}", bad);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,236 @@
+//! Date expressions accepted wherever a command takes a date.
+//!
+//! | Expression | Meaning |
+//! |------------|---------|
+//! | `2025-11-20` | That day |
+//! | `11-20` | November 20 this year |
+//! | `today`, `tomorrow`, `yesterday` | Relative to today |
+//! | `+3d`, `-1w`, `+2m`, `+1y` | Days, weeks, months or years from today |
+//! | `fri`, `friday` | Today if it is a Friday, else the next one |
+//! | `next fri` | A week after `fri`, skipping the coming Friday |
+//! | `last fri` | The first Friday before today |
+//! | `eom` | Last day of this month |
+//! | `2025-W47`, `2025-W47-5` | Monday (or the given weekday) of an ISO week |
+//!
+//! A month is `2026-03` or any of the above, standing for its month.
+//!
+//! A [`DateRange`] is two expressions joined by `..` (`2025-11-01..eom`,
+//! `-1w..`); a missing side means today.
+
+use anyhow::{bail, Context, Result};
+use chrono::{Datelike, Days, Months, NaiveDate, Weekday};
+
+use crate::clock::Clock;
+use crate::recur::parse_weekday;
+
+/// Parse a date expression relative to the local today.
+pub fn parse_date(s: &str) -> Result<NaiveDate> {
+	parse_date_at(s, Clock::system().today())
+}
+
+/// Parse a date expression relative to `today`.
+pub fn parse_date_at(s: &str, today: NaiveDate) -> Result<NaiveDate> {
+	let s = s.trim().to_lowercase();
+	expression(&s, today).with_context(|| format!("invalid date: {} (try YYYY-MM-DD, MM-DD, today, +3d, fri, next mon, eom or 2025-W47)", s))
+}
+
+/// Parse a month, `YYYY-MM` or any date expression, relative to `today`.
+/// Months are represented by their first day.
+pub fn parse_month_at(s: &str, today: NaiveDate) -> Result<NaiveDate> {
+	if let Ok(d) = NaiveDate::parse_from_str(&format!("{}-01", s.trim()), "%Y-%m-%d") { return Ok(d); }
+	let d = parse_date_at(s, today).with_context(|| format!("invalid month: {} (try YYYY-MM, +1m or a date)", s.trim()))?;
+	Ok(month_of(d))
+}
+
+/// First day of the month containing `d`.
+pub fn month_of(d: NaiveDate) -> NaiveDate {
+	d.with_day(1).expect("every month has a first day")
+}
+
+/// An inclusive span of days.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateRange {
+	pub from: NaiveDate,
+	pub to: NaiveDate,
+}
+
+impl DateRange {
+	pub fn new(from: NaiveDate, to: NaiveDate) -> Result<Self> {
+		if to < from { bail!("range ends before it starts: {}..{}", from, to); }
+		Ok(DateRange { from, to })
+	}
+
+	pub fn day(date: NaiveDate) -> Self {
+		DateRange { from: date, to: date }
+	}
+
+	/// Parse `FROM..TO` relative to the local today.
+	pub fn parse(s: &str) -> Result<Self> {
+		Self::parse_at(s, Clock::system().today())
+	}
+
+	/// Parse `FROM..TO` relative to `today`; either side may be left out.
+	pub fn parse_at(s: &str, today: NaiveDate) -> Result<Self> {
+		let (from, to) = s.split_once("..").with_context(|| format!("invalid range: {} (expected FROM..TO)", s))?;
+		let side = |e: &str| if e.trim().is_empty() { Ok(today) } else { parse_date_at(e, today) };
+		Self::new(side(from)?, side(to)?)
+	}
+
+	pub fn days(&self) -> impl Iterator<Item = NaiveDate> {
+		let to = self.to;
+		self.from.iter_days().take_while(move |d| *d <= to)
+	}
+
+	pub fn contains(&self, date: NaiveDate) -> bool {
+		self.from <= date && date <= self.to
+	}
+
+	pub fn is_single_day(&self) -> bool {
+		self.from == self.to
+	}
+}
+
+impl std::fmt::Display for DateRange {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		if self.is_single_day() { write!(f, "{}", self.from) } else { write!(f, "{}..{}", self.from, self.to) }
+	}
+}
+
+fn expression(s: &str, today: NaiveDate) -> Option<NaiveDate> {
+	if let Ok(d) = NaiveDate::parse_from_str(s, "%Y-%m-%d") { return Some(d); }
+	match s {
+		"today" => return Some(today),
+		"tomorrow" => return today.succ_opt(),
+		"yesterday" => return today.pred_opt(),
+		"eom" => return (today.with_day(1)? + Months::new(1)).pred_opt(),
+		_ => {}
+	}
+	if let Some(rest) = s.strip_prefix('+') { return offset(today, rest, true); }
+	if let Some(rest) = s.strip_prefix('-') { return offset(today, rest, false); }
+	if let Some(day) = s.strip_prefix("next ").and_then(parse_weekday) { return coming(today, day).checked_add_days(Days::new(7)); }
+	if let Some(day) = s.strip_prefix("last ").and_then(parse_weekday) { return Some(before(today, day)); }
+	if let Some(day) = parse_weekday(s) { return Some(coming(today, day)); }
+	if let Some(d) = iso_week(s) { return Some(d); }
+	let (month, day) = s.split_once('-')?;
+	NaiveDate::from_ymd_opt(today.year(), month.parse().ok()?, day.parse().ok()?)
+}
+
+/// `3d`, `1w`, `2m` or `1y` added to (or taken from) `today`.
+fn offset(today: NaiveDate, s: &str, forward: bool) -> Option<NaiveDate> {
+	let unit = s.chars().last()?;
+	let n: u32 = s[..s.len() - unit.len_utf8()].parse().ok()?;
+	let (days, months) = match unit {
+		'd' => (n, 0),
+		'w' => (n.checked_mul(7)?, 0),
+		'm' => (0, n),
+		'y' => (0, n.checked_mul(12)?),
+		_ => return None,
+	};
+	if forward {
+		today.checked_add_days(Days::new(days.into()))?.checked_add_months(Months::new(months))
+	} else {
+		today.checked_sub_days(Days::new(days.into()))?.checked_sub_months(Months::new(months))
+	}
+}
+
+/// `today` if it falls on `day`, else the first `day` after it.
+fn coming(today: NaiveDate, day: Weekday) -> NaiveDate {
+	let ahead = (day.num_days_from_monday() + 7 - today.weekday().num_days_from_monday()) % 7;
+	today + Days::new(ahead.into())
+}
+
+fn before(today: NaiveDate, day: Weekday) -> NaiveDate {
+	let back = (today.weekday().num_days_from_monday() + 7 - day.num_days_from_monday() - 1) % 7 + 1;
+	today - Days::new(back.into())
+}
+
+/// `2025-W47` (its Monday) or `2025-W47-5` (its Friday).
+fn iso_week(s: &str) -> Option<NaiveDate> {
+	let (year, rest) = s.split_once("-w")?;
+	let (week, day) = match rest.split_once('-') {
+		Some((w, d)) => (w, d.parse().ok()?),
+		None => (rest, 1),
+	};
+	let day = Weekday::try_from(u8::checked_sub(day, 1)?).ok()?;
+	NaiveDate::from_isoywd_opt(year.parse().ok()?, week.parse().ok()?, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(y: i32, m: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, day).unwrap()
+    }
+
+    #[test]
+    fn test_parse_date_expressions() -> Result<()> {
+        // A Wednesday.
+        let today = d(2025, 11, 19);
+        let cases = [
+            ("2025-11-20", d(2025, 11, 20)),
+            ("today", today),
+            ("Tomorrow", d(2025, 11, 20)),
+            ("yesterday", d(2025, 11, 18)),
+            ("+3d", d(2025, 11, 22)),
+            ("-1w", d(2025, 11, 12)),
+            ("+1m", d(2025, 12, 19)),
+            ("+1y", d(2026, 11, 19)),
+            ("fri", d(2025, 11, 21)),
+            ("wednesday", today),
+            ("mon", d(2025, 11, 24)),
+            ("next wed", d(2025, 11, 26)),
+            ("next fri", d(2025, 11, 28)),
+            ("next mon", d(2025, 12, 1)),
+            ("last wed", d(2025, 11, 12)),
+            ("last mon", d(2025, 11, 17)),
+            ("eom", d(2025, 11, 30)),
+            ("2025-W47", d(2025, 11, 17)),
+            ("2025-w47-5", d(2025, 11, 21)),
+            ("2026-W01", d(2025, 12, 29)),
+            ("11-20", d(2025, 11, 20)),
+            ("2-28", d(2025, 2, 28)),
+        ];
+        for (input, want) in cases {
+            assert_eq!(parse_date_at(input, today)?, want, "{}", input);
+        }
+        let saturday = d(2025, 11, 22);
+        assert_eq!((parse_date_at("fri", saturday)?, parse_date_at("next fri", saturday)?), (d(2025, 11, 28), d(2025, 12, 5)), "next skips the coming Friday");
+        assert_eq!(parse_date_at("eom", d(2025, 12, 3))?, d(2025, 12, 31), "eom in December");
+        assert_eq!(parse_date_at("+1m", d(2025, 1, 31))?, d(2025, 2, 28), "Months clamp to the last day");
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_range() -> Result<()> {
+        let today = d(2025, 11, 19);
+        let r = DateRange::parse_at("2025-11-01..2025-11-07", today)?;
+        assert_eq!(r.days().count(), 7);
+        assert_eq!(DateRange::parse_at("fri..+9d", today)?, DateRange::new(d(2025, 11, 21), d(2025, 11, 28))?);
+        assert_eq!(DateRange::parse_at("-1w..", today)?, DateRange::new(d(2025, 11, 12), today)?, "An open end means today");
+        assert_eq!(DateRange::parse_at("..", today)?, DateRange::day(today));
+        assert!(DateRange::parse_at("mon..fri", today).is_err(), "A backwards range is rejected");
+        assert!(DateRange::parse_at("2025-11-01", today).is_err(), "A range needs ..");
+        assert_eq!(r.to_string(), "2025-11-01..2025-11-07");
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_month() -> Result<()> {
+        let today = d(2025, 11, 19);
+        assert_eq!(parse_month_at("2026-03", today)?, d(2026, 3, 1));
+        assert_eq!(parse_month_at("+2m", today)?, d(2026, 1, 1));
+        assert_eq!(parse_month_at("today", today)?, d(2025, 11, 1));
+        assert_eq!(parse_month_at("2025-12-24", today)?, d(2025, 12, 1));
+        assert!(parse_month_at("2026-13", today).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_date_rejects_nonsense() {
+        let today = d(2025, 11, 19);
+        for bad in ["", "someday", "+3x", "+d", "next", "next month", "2025-W54", "2025-W47-8", "13-01", "02-30", "2025-13-01"] {
+            assert!(parse_date_at(bad, today).is_err(), "Should reject {:?}", bad);
+        }
+    }
+}
@@ -0,0 +1,234 @@
+//! Machine-readable output for scripts.
+//!
+//! Every format carries one record per bullet with these fields, in order:
+//!
+//! | field            | type                 | example        |
+//! |------------------|----------------------|----------------|
+//! | `date`           | `YYYY-MM-DD`, `YYYY-MM` or `@collection` | `2025-11-06` |
+//! | `id`             | string or null       | `k3x9`         |
+//! | `index`          | 1-based position     | `2`            |
+//! | `kind`           | task, event, note    | `task`         |
+//! | `state`          | open, done, migrated, scheduled, cancelled | `open` |
+//! | `text`           | string               | `Review PRs`   |
+//! | `priority`       | 1 (low) – 3 (high) or null | `3`      |
+//! | `tags`           | list of strings      | `["work"]`     |
+//! | `notes`          | list of strings      | `["prep"]`     |
+//! | `meeting_time`   | `HH:MM` or null      | `10:00`        |
+//! | `duration_min`   | minutes or null      | `30`           |
+//! | `recurring`      | bool                 | `false`        |
+//! | `migrated_from`  | `YYYY-MM-DD`, `YYYY-MM`, `@collection` or null | |
+//! | `migrated_to`    | `YYYY-MM-DD`, `YYYY-MM`, `@collection` or null | |
+//! | `meeting_tz`     | IANA zone or null    | `Europe/Berlin` |
+//!
+//! `meeting_time` is as written in the day file, in `meeting_tz` when set.
+//!
+//! JSON is an array of objects with null for missing values. CSV and TSV
+//! start with a header row and leave missing values empty; tags are joined
+//! with spaces. In CSV, notes are joined with newlines and fields are quoted
+//! as needed (RFC 4180). In TSV, notes are joined with `\n` and backslash,
+//! tab and newline are written as `\\`, `\t` and `\n`.
+
+use std::fmt;
+use std::io::Write;
+use std::str::FromStr;
+
+use anyhow::{bail, Result};
+use serde::Serialize;
+
+use crate::search::{Page, SearchHit};
+
+/// Field names, in output order.
+pub const FIELDS: [&str; 15] = [
+	"date", "id", "index", "kind", "state", "text", "priority", "tags", "notes",
+	"meeting_time", "duration_min", "recurring", "migrated_from", "migrated_to", "meeting_tz",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+	Json,
+	Csv,
+	Tsv,
+}
+
+impl FromStr for Format {
+	type Err = anyhow::Error;
+
+	fn from_str(s: &str) -> Result<Self> {
+		match s.to_ascii_lowercase().as_str() {
+			"json" => Ok(Format::Json),
+			"csv" => Ok(Format::Csv),
+			"tsv" => Ok(Format::Tsv),
+			_ => bail!("unknown format: {} (expected json, csv or tsv)", s),
+		}
+	}
+}
+
+impl fmt::Display for Format {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(match self {
+			Format::Json => "json",
+			Format::Csv => "csv",
+			Format::Tsv => "tsv",
+		})
+	}
+}
+
+#[derive(Serialize)]
+struct Record<'a> {
+	date: String,
+	id: Option<&'a str>,
+	index: usize,
+	kind: &'static str,
+	state: &'static str,
+	text: &'a str,
+	priority: Option<u8>,
+	tags: &'a [String],
+	notes: &'a [String],
+	meeting_time: Option<String>,
+	duration_min: Option<u32>,
+	recurring: bool,
+	migrated_from: Option<String>,
+	migrated_to: Option<String>,
+	meeting_tz: Option<String>,
+}
+
+impl<'a> From<&'a SearchHit> for Record<'a> {
+	fn from(h: &'a SearchHit) -> Self {
+		let b = &h.bullet;
+		Record {
+			date: match &h.page {
+				Page::Day => h.date.to_string(),
+				Page::Month => h.date.format("%Y-%m").to_string(),
+				Page::Collection(name) => format!("@{}", name),
+			},
+			id: b.id.as_deref(),
+			index: b.visible_index,
+			kind: b.kind.as_str(),
+			state: b.state.as_str(),
+			text: &b.text,
+			priority: b.priority,
+			tags: &b.tags,
+			notes: &b.notes,
+			meeting_time: b.meeting_time.map(|t| t.format("%H:%M").to_string()),
+			duration_min: b.meeting_time.and(b.meeting_duration_min),
+			recurring: b.recurring,
+			migrated_from: b.came_from().map(|l| l.to_string()),
+			migrated_to: b.went_to().map(|l| l.to_string()),
+			meeting_tz: b.meeting_time.and(b.meeting_tz).map(|tz| tz.to_string()),
+		}
+	}
+}
+
+impl Record<'_> {
+	/// Field values in [`FIELDS`] order, with lists joined by `notes_sep`.
+	fn values(&self, notes_sep: &str) -> [String; 15] {
+		let opt = |v: Option<String>| v.unwrap_or_default();
+		[
+			self.date.clone(),
+			opt(self.id.map(str::to_string)),
+			self.index.to_string(),
+			self.kind.to_string(),
+			self.state.to_string(),
+			self.text.to_string(),
+			opt(self.priority.map(|p| p.to_string())),
+			self.tags.join(" "),
+			self.notes.join(notes_sep),
+			opt(self.meeting_time.clone()),
+			opt(self.duration_min.map(|d| d.to_string())),
+			self.recurring.to_string(),
+			opt(self.migrated_from.clone()),
+			opt(self.migrated_to.clone()),
+			opt(self.meeting_tz.clone()),
+		]
+	}
+}
+
+/// Write `hits` to `out` in `format`.
+pub fn write_bullets(out: &mut impl Write, format: Format, hits: &[SearchHit]) -> Result<()> {
+	let records: Vec<Record> = hits.iter().map(Record::from).collect();
+	match format {
+		Format::Json => {
+			serde_json::to_writer_pretty(&mut *out, &records)?;
+			writeln!(out)?;
+		}
+		Format::Csv => {
+			writeln!(out, "{}", FIELDS.join(","))?;
+			for r in &records {
+				let row: Vec<String> = r.values("\n").iter().map(|v| csv_field(v)).collect();
+				writeln!(out, "{}", row.join(","))?;
+			}
+		}
+		Format::Tsv => {
+			writeln!(out, "{}", FIELDS.join("\t"))?;
+			for r in &records {
+				let row: Vec<String> = r.values("\n").iter().map(|v| tsv_field(v)).collect();
+				writeln!(out, "{}", row.join("\t"))?;
+			}
+		}
+	}
+	Ok(())
+}
+
+fn csv_field(v: &str) -> String {
+	if v.contains([',', '"', '\n', '\r']) {
+		format!("\"{}\"", v.replace('"', "\"\""))
+	} else {
+		v.to_string()
+	}
+}
+
+fn tsv_field(v: &str) -> String {
+	v.replace('\\', "\\\\").replace('\t', "\\t").replace('\n', "\\n").replace('\r', "\\r")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bullet::parse_bullets;
+    use chrono::NaiveDate;
+
+    fn hits() -> Vec<SearchHit> {
+        let lines = vec![
+            "- [ ] [mtg 10:00 30] (!!) Sync, \"weekly\" #work ^k3x9".to_string(),
+            "  - note: agenda".to_string(),
+            "  - note: tab\there".to_string(),
+            "- [x] Done thing ← 2025-11-05".to_string(),
+        ];
+        let date = NaiveDate::from_ymd_opt(2025, 11, 6).unwrap();
+        parse_bullets(&lines).into_iter().map(|bullet| SearchHit { date, page: Page::Day, bullet }).collect()
+    }
+
+    #[test]
+    fn test_export_formats() -> Result<()> {
+        let mut json = Vec::new();
+        write_bullets(&mut json, Format::Json, &hits())?;
+        let v: serde_json::Value = serde_json::from_slice(&json)?;
+        assert_eq!(v[0]["id"], "k3x9");
+        assert_eq!(v[0]["meeting_time"], "10:00");
+        assert_eq!(v[0]["duration_min"], 30);
+        assert!(v[0]["meeting_tz"].is_null(), "Local meetings have no zone");
+        assert_eq!(v[0]["priority"], 2);
+        assert_eq!(v[0]["notes"][1], "tab\there");
+        assert_eq!(v[1]["state"], "done");
+        assert!(v[1]["id"].is_null() && v[1]["priority"].is_null(), "Missing values should be null");
+        assert_eq!(v[1]["migrated_from"], "2025-11-05");
+        let keys: Vec<&str> = v[0].as_object().unwrap().keys().map(|k| k.as_str()).collect();
+        assert_eq!(keys.len(), FIELDS.len(), "JSON should carry every documented field");
+
+        let mut csv = Vec::new();
+        write_bullets(&mut csv, Format::Csv, &hits())?;
+        let csv = String::from_utf8(csv)?;
+        assert!(csv.starts_with("date,id,index,kind,state,text,"), "CSV should start with the header");
+        assert!(csv.contains(",\"Sync, \"\"weekly\"\"\",2,work,\"agenda\ntab\there\",10:00,30,false,,"), "CSV should quote as needed: {}", csv);
+
+        let mut tsv = Vec::new();
+        write_bullets(&mut tsv, Format::Tsv, &hits())?;
+        let tsv = String::from_utf8(tsv)?;
+        assert_eq!(tsv.lines().count(), 3, "TSV rows must stay on one line");
+        assert!(tsv.contains("\tagenda\\ntab\\there\t"), "TSV should escape tabs and newlines: {}", tsv);
+
+        assert_eq!("CSV".parse::<Format>()?, Format::Csv);
+        assert!("xml".parse::<Format>().is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,697 @@
+//! iCalendar (RFC 5545) export and import.
+
+use std::collections::BTreeMap;
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+
+use crate::bullet::{plain_text, to_local, zoned, Bullet, DEFAULT_MEETING_MIN, BulletRef, Kind, State};
+use crate::journal::{read_file_lines, Journal};
+use crate::recur::Rule;
+use crate::search::{Page, SearchHit};
+
+impl Journal {
+	/// Meetings between `from` and `to` (inclusive) as an iCalendar document,
+	/// plus open tasks as to-dos when `include_tasks` is set. UIDs depend
+	/// only on the bullet and its day, so regenerating the file updates
+	/// events in subscribed calendars instead of duplicating them.
+	pub fn export_ics(&self, from: NaiveDate, to: NaiveDate, include_tasks: bool) -> Result<String> {
+		if to < from { bail!("--to {} is before --from {}", to, from); }
+		let mut hits = Vec::new();
+		for date in from.iter_days().take_while(|d| *d <= to) {
+			hits.extend(self.bullets(date)?.into_iter().map(|bullet| SearchHit { date, page: Page::Day, bullet }));
+		}
+		Ok(calendar(&hits, include_tasks, self.clock().now_utc()))
+	}
+}
+
+pub(crate) fn calendar(hits: &[SearchHit], include_tasks: bool, stamp: DateTime<Utc>) -> String {
+	let stamp = stamp.format("%Y%m%dT%H%M%SZ").to_string();
+	let mut lines = vec![
+		"BEGIN:VCALENDAR".to_string(),
+		"VERSION:2.0".to_string(),
+		"PRODID:-//bullet-journal//bj//EN".to_string(),
+		"CALSCALE:GREGORIAN".to_string(),
+		"X-WR-CALNAME:Bullet Journal".to_string(),
+	];
+	for h in hits {
+		let b = &h.bullet;
+		// Migrated and scheduled lines are pointers; the live copy is exported from its own day.
+		if matches!(b.state, State::Migrated | State::Scheduled) { continue; }
+		if let Some(t) = b.meeting_time {
+			lines.push("BEGIN:VEVENT".to_string());
+			lines.push(format!("UID:{}", uid(h)));
+			lines.push(format!("DTSTAMP:{}", stamp));
+			let start = h.date.and_time(t);
+			// Zoned meetings are exported in UTC so no VTIMEZONE is needed.
+			match b.meeting_tz.and_then(|tz| zoned(start, tz)) {
+				Some(z) => lines.push(format!("DTSTART:{}", z.with_timezone(&Utc).format("%Y%m%dT%H%M%SZ"))),
+				None => lines.push(format!("DTSTART:{}", start.format("%Y%m%dT%H%M%S"))),
+			}
+			lines.push(format!("DURATION:PT{}M", b.meeting_duration_min.unwrap_or(DEFAULT_MEETING_MIN)));
+			lines.push(format!("STATUS:{}", if b.state == State::Cancelled { "CANCELLED" } else { "CONFIRMED" }));
+			push_details(&mut lines, b);
+			lines.push("END:VEVENT".to_string());
+		} else if include_tasks && b.kind == Kind::Task && b.is_open() {
+			lines.push("BEGIN:VTODO".to_string());
+			lines.push(format!("UID:{}", uid(h)));
+			lines.push(format!("DTSTAMP:{}", stamp));
+			lines.push(format!("DUE;VALUE=DATE:{}", h.date.format("%Y%m%d")));
+			lines.push("STATUS:NEEDS-ACTION".to_string());
+			if let Some(p) = b.priority {
+				// iCalendar priorities run 1 (highest) to 9 (lowest).
+				lines.push(format!("PRIORITY:{}", match p { 3 => 1, 2 => 5, _ => 9 }));
+			}
+			push_details(&mut lines, b);
+			lines.push("END:VTODO".to_string());
+		}
+	}
+	lines.push("END:VCALENDAR".to_string());
+	lines.iter().map(|l| fold(l)).collect()
+}
+
+fn push_details(lines: &mut Vec<String>, b: &Bullet) {
+	lines.push(format!("SUMMARY:{}", escape(&b.text)));
+	if !b.tags.is_empty() {
+		lines.push(format!("CATEGORIES:{}", b.tags.iter().map(|t| escape(t)).collect::<Vec<_>>().join(",")));
+	}
+	if !b.notes.is_empty() {
+		lines.push(format!("DESCRIPTION:{}", escape(&b.notes.join("\n"))));
+	}
+}
+
+/// Stable UID: the bullet ID (or its position for legacy bullets) plus the day,
+/// so each occurrence of a recurring meeting is its own event.
+fn uid(h: &SearchHit) -> String {
+	let who = h.bullet.id.clone().unwrap_or_else(|| format!("n{}", h.bullet.visible_index));
+	format!("{}-{}@bullet-journal", who, h.date.format("%Y%m%d"))
+}
+
+/// Escape a TEXT value.
+fn escape(s: &str) -> String {
+	s.replace('\\', "\\\\").replace(';', "\\;").replace(',', "\\,").replace('\n', "\\n")
+}
+
+/// Fold a content line to 75 octets and terminate it with CRLF.
+fn fold(line: &str) -> String {
+	let mut out = String::new();
+	let mut width = 0;
+	for c in line.chars() {
+		if width + c.len_utf8() > 75 {
+			out.push_str("\r\n ");
+			width = 1;
+		}
+		out.push(c);
+		width += c.len_utf8();
+	}
+	out.push_str("\r\n");
+	out
+}
+
+/// What [`Journal::import_ics`] did.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportReport {
+	pub added: usize,
+	pub updated: usize,
+	pub unchanged: usize,
+	pub cancelled: usize,
+	/// Human-readable notes about events that were skipped or simplified.
+	pub warnings: Vec<String>,
+}
+
+/// A DTSTART/DTEND value as written in the file.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum When {
+	Date(NaiveDate),
+	Floating(NaiveDateTime),
+	Utc(NaiveDateTime),
+	Zoned(NaiveDateTime, Tz),
+}
+
+impl When {
+	/// The date the value falls on in its own zone; recurrence is evaluated here.
+	fn date(&self) -> NaiveDate {
+		match self {
+			When::Date(d) => *d,
+			When::Floating(dt) | When::Utc(dt) | When::Zoned(dt, _) => dt.date(),
+		}
+	}
+
+	/// The same wall-clock time moved to another day.
+	fn on(&self, date: NaiveDate) -> When {
+		match *self {
+			When::Date(_) => When::Date(date),
+			When::Floating(dt) => When::Floating(date.and_time(dt.time())),
+			When::Utc(dt) => When::Utc(date.and_time(dt.time())),
+			When::Zoned(dt, tz) => When::Zoned(date.and_time(dt.time()), tz),
+		}
+	}
+
+	/// Local date and time (None for all-day values).
+	fn local(&self) -> Option<NaiveDateTime> {
+		match *self {
+			When::Date(_) => None,
+			When::Floating(dt) => Some(dt),
+			When::Utc(dt) => Some(Utc.from_utc_datetime(&dt).with_timezone(&Local).naive_local()),
+			When::Zoned(dt, tz) => Some(to_local(dt, tz)),
+		}
+	}
+}
+
+#[derive(Debug, Clone, Default)]
+struct VEvent {
+	uid: String,
+	summary: String,
+	start: Option<When>,
+	end: Option<When>,
+	duration_min: Option<u32>,
+	rrule: Option<String>,
+	exdates: Vec<NaiveDate>,
+	recurrence_id: Option<NaiveDate>,
+	categories: Vec<String>,
+	description: Option<String>,
+	cancelled: bool,
+}
+
+/// One dated instance of an event, keyed by its UID and, for recurring
+/// events, the day of the occurrence in the series.
+#[derive(Debug, Clone, PartialEq)]
+struct Occurrence {
+	key: (String, Option<NaiveDate>),
+	date: NaiveDate,
+	time: Option<NaiveTime>,
+	tz: Option<Tz>,
+	duration_min: u32,
+	title: String,
+	tags: Vec<String>,
+	notes: Vec<String>,
+	cancelled: bool,
+}
+
+impl Journal {
+	/// Import the VEVENTs in `ics` that fall between `from` and `until` as
+	/// meetings (all-day events become `[o]` events), expanding recurring
+	/// events on those days. Each occurrence is remembered by UID, so
+	/// importing the same calendar again moves or updates existing bullets
+	/// instead of adding duplicates, and leaves alone bullets that were
+	/// deleted from the journal. Events that can't be read are skipped with
+	/// a warning.
+	pub fn import_ics(&self, ics: &str, from: NaiveDate, until: NaiveDate) -> Result<ImportReport> {
+		if until < from { bail!("--until {} is before --from {}", until, from); }
+		let mut report = ImportReport::default();
+		let events = parse_events(ics, &mut report.warnings)?;
+		let occurrences = expand(&events, from, until, &mut report.warnings);
+		self.operation("import", || {
+			let path = self.imported_uids_path();
+			let mut known = read_imported(&read_file_lines(&path)?);
+			let before = known.clone();
+			let applied = self.apply_occurrences(occurrences, &mut known, &mut report);
+			// Remember what did land, even if an event failed part-way.
+			if known != before { self.write_lines(&path, &write_imported(&known))?; }
+			applied?;
+			Ok(report)
+		})
+	}
+
+	/// Add, move, update or cancel the bullet of each occurrence, keeping
+	/// `known` in step with every change made.
+	fn apply_occurrences(&self, occurrences: Vec<Occurrence>, known: &mut Imported, report: &mut ImportReport) -> Result<()> {
+		for occ in occurrences {
+			match known.get(&occ.key).cloned() {
+				None if occ.cancelled => {}
+				None => {
+					let notes = occ.notes.clone();
+					let b = match occ.time {
+						Some(t) => self.add_meeting(occ.date, t, occ.duration_min, occ.tz, &occ.title, &occ.tags, &notes)?,
+						None => self.add_entry(occ.date, Kind::Event, &occ.title, None, &occ.tags, &notes)?,
+					};
+					known.insert(occ.key.clone(), (occ.date, b.id.expect("new bullets have IDs")));
+					report.added += 1;
+				}
+				Some((date, id)) => {
+					let Some(existing) = self.bullets(date)?.into_iter().find(|b| b.id.as_deref() == Some(id.as_str())) else {
+						// Deleted in the journal: respect that.
+						report.unchanged += 1;
+						continue;
+					};
+					if occ.cancelled {
+						match (existing.kind, existing.state) {
+							(Kind::Task, State::Open) => { self.cancel_bullet(date, BulletRef::Id(id))?; }
+							// Events have no cancelled signifier; drop them.
+							(Kind::Event, _) => { self.delete_bullet(date, BulletRef::Id(id))?; }
+							_ => { report.unchanged += 1; continue; }
+						}
+						report.cancelled += 1;
+						continue;
+					}
+					let wanted = Bullet {
+						text: occ.title.clone(),
+						tags: occ.tags.clone(),
+						notes: occ.notes.clone(),
+						meeting_time: occ.time,
+						meeting_duration_min: occ.time.map(|_| occ.duration_min),
+						meeting_tz: occ.tz,
+						..existing.clone()
+					};
+					if date != occ.date {
+						self.delete_bullet(date, BulletRef::Id(id.clone()))?;
+						let mut block = vec![wanted.to_line()];
+						block.extend(wanted.notes.iter().map(|n| format!("  - note: {}", n)));
+						self.append_bullet(occ.date, block)?;
+						known.insert(occ.key.clone(), (occ.date, id));
+						report.updated += 1;
+					} else if wanted != existing {
+						self.update_bullet(date, BulletRef::Id(id), |b| *b = wanted)?;
+						report.updated += 1;
+					} else {
+						report.unchanged += 1;
+					}
+				}
+			}
+		}
+		Ok(())
+	}
+
+	fn imported_uids_path(&self) -> std::path::PathBuf { self.root().join("imported.uids") }
+}
+
+type Imported = BTreeMap<(String, Option<NaiveDate>), (NaiveDate, String)>;
+
+/// `UID<TAB>occurrence day or -<TAB>day in the journal<TAB>bullet ID` per line.
+fn read_imported(lines: &[String]) -> Imported {
+	let mut out = BTreeMap::new();
+	for line in lines {
+		let parts: Vec<&str> = line.split('\t').collect();
+		let [uid, occ, date, id] = parts[..] else { continue };
+		let Ok(date) = NaiveDate::parse_from_str(date, "%Y-%m-%d") else { continue };
+		let occ = NaiveDate::parse_from_str(occ, "%Y-%m-%d").ok();
+		out.insert((uid.to_string(), occ), (date, id.to_string()));
+	}
+	out
+}
+
+fn write_imported(known: &Imported) -> Vec<String> {
+	known.iter().map(|((uid, occ), (date, id))| {
+		let occ = occ.map_or("-".to_string(), |d| d.to_string());
+		format!("{}\t{}\t{}\t{}", uid, occ, date, id)
+	}).collect()
+}
+
+/// Turn events into the occurrences dated between `from` and `until`.
+/// Overrides (`RECURRENCE-ID`) replace the occurrence they stand for, or
+/// drop it when moved out of the window.
+fn expand(events: &[VEvent], from: NaiveDate, until: NaiveDate, warnings: &mut Vec<String>) -> Vec<Occurrence> {
+	let mut out: Vec<Occurrence> = Vec::new();
+	let in_window = |occ: &Occurrence| (from..=until).contains(&occ.date);
+	let mut overrides = Vec::new();
+	for ev in events {
+		let Some(start) = ev.start else {
+			warnings.push(format!("skipped \"{}\": no DTSTART", ev.summary));
+			continue;
+		};
+		if ev.recurrence_id.is_some() { overrides.push((ev, start)); continue; }
+		let rule = match ev.rrule.as_deref().map(Rule::parse) {
+			None => None,
+			Some(Ok(rule)) => Some(rule),
+			Some(Err(e)) => {
+				warnings.push(format!("\"{}\": {}; imported the first occurrence only", ev.summary, e));
+				None
+			}
+		};
+		match rule {
+			None => out.extend(Some(occurrence(ev, None, start)).filter(in_window)),
+			Some(rule) => {
+				let first = start.date();
+				for day in first.iter_days().take_while(|d| *d <= until) {
+					if day < from || !rule.occurs_on(first, day) || ev.exdates.contains(&day) { continue; }
+					out.push(occurrence(ev, Some(day), start.on(day)));
+				}
+			}
+		}
+	}
+	for (ev, start) in overrides {
+		let occ = occurrence(ev, ev.recurrence_id, start);
+		let slot = out.iter().position(|o| o.key == occ.key);
+		match (slot, in_window(&occ)) {
+			(Some(i), true) => out[i] = occ,
+			(Some(i), false) => { out.remove(i); }
+			(None, true) => out.push(occ),
+			(None, false) => {}
+		}
+	}
+	out
+}
+
+fn occurrence(ev: &VEvent, in_series: Option<NaiveDate>, start: When) -> Occurrence {
+	// Zoned times keep their zone; UTC times are shown in local time.
+	let (date, time, tz) = match start {
+		When::Zoned(dt, tz) => (dt.date(), Some(dt.time()), Some(tz)),
+		_ => {
+			let local = start.local();
+			(local.map_or(start.date(), |dt| dt.date()), local.map(|dt| dt.time()), None)
+		}
+	};
+	let duration_min = ev.duration_min.unwrap_or_else(|| match (ev.end.and_then(|e| e.local()), ev.start.and_then(|s| s.local())) {
+		(Some(end), Some(begin)) => (end - begin).num_minutes().max(0) as u32,
+		_ => 0,
+	});
+	Occurrence {
+		key: (ev.uid.clone(), in_series),
+		date,
+		time,
+		tz,
+		duration_min,
+		title: if ev.summary.trim().is_empty() { "(no title)".to_string() } else { plain_text(&ev.summary) },
+		tags: ev.categories.clone(),
+		notes: ev.description.as_deref().map(|d| d.lines().map(str::trim).filter(|l| !l.is_empty()).map(str::to_string).collect()).unwrap_or_default(),
+		cancelled: ev.cancelled,
+	}
+}
+
+/// Parse every VEVENT in a calendar. Nested components such as VALARM are
+/// ignored, and events with a missing UID or an unreadable value are skipped
+/// with a warning.
+fn parse_events(ics: &str, warnings: &mut Vec<String>) -> Result<Vec<VEvent>> {
+	let mut lines: Vec<String> = Vec::new();
+	for raw in ics.lines() {
+		match (raw.strip_prefix(' ').or_else(|| raw.strip_prefix('\t')), lines.last_mut()) {
+			(Some(cont), Some(prev)) => prev.push_str(cont),
+			_ => lines.push(raw.to_string()),
+		}
+	}
+	if !lines.iter().any(|l| l.trim().eq_ignore_ascii_case("BEGIN:VCALENDAR")) { bail!("not an iCalendar file"); }
+
+	let mut events = Vec::new();
+	let mut current: Option<VEvent> = None;
+	let mut error = None;
+	let mut depth = 0;
+	for line in &lines {
+		let Some((name, params, value)) = split_property(line) else { continue };
+		match (name.as_str(), current.as_mut()) {
+			("BEGIN", None) if value.eq_ignore_ascii_case("VEVENT") => { current = Some(VEvent::default()); error = None; depth = 0; }
+			("BEGIN", Some(_)) => depth += 1,
+			("END", Some(_)) if depth > 0 => depth -= 1,
+			("END", Some(_)) if value.eq_ignore_ascii_case("VEVENT") => {
+				let ev = current.take().expect("inside VEVENT");
+				match error.take() {
+					Some(e) => warnings.push(format!("skipped \"{}\": {}", ev.summary, e)),
+					None if ev.uid.is_empty() => warnings.push(format!("skipped \"{}\": no UID", ev.summary)),
+					None => events.push(ev),
+				}
+			}
+			(_, Some(ev)) if depth == 0 => {
+				if let Err(e) = read_property(ev, &name, &params, value) { error.get_or_insert(e); }
+			}
+			_ => {}
+		}
+	}
+	Ok(events)
+}
+
+type Params = Vec<(String, String)>;
+
+fn read_property(ev: &mut VEvent, name: &str, params: &Params, value: String) -> Result<()> {
+	let param = |key: &str| params.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str());
+	match name {
+		"UID" => ev.uid = value.trim().to_string(),
+		"SUMMARY" => ev.summary = unescape(&value),
+		"DESCRIPTION" => ev.description = Some(unescape(&value)),
+		"DTSTART" => ev.start = Some(parse_when(&value, param("TZID"))?),
+		"DTEND" => ev.end = Some(parse_when(&value, param("TZID"))?),
+		"DURATION" => ev.duration_min = Some(parse_duration(&value)?),
+		"RRULE" => ev.rrule = Some(value),
+		"EXDATE" => {
+			for v in value.split(',') { ev.exdates.push(parse_when(v, param("TZID"))?.date()); }
+		}
+		"RECURRENCE-ID" => ev.recurrence_id = Some(parse_when(&value, param("TZID"))?.date()),
+		"CATEGORIES" => ev.categories.extend(split_text_list(&value).into_iter().map(|c| c.split_whitespace().collect::<Vec<_>>().join("-")).filter(|c| !c.is_empty())),
+		"STATUS" => ev.cancelled = value.eq_ignore_ascii_case("CANCELLED"),
+		_ => {}
+	}
+	Ok(())
+}
+
+/// Split `NAME;PARAM=V;PARAM="V":value` into its upper-cased name, params and value.
+fn split_property(line: &str) -> Option<(String, Params, String)> {
+	let mut in_quotes = false;
+	let colon = line.char_indices().find(|&(_, c)| {
+		if c == '"' { in_quotes = !in_quotes; }
+		c == ':' && !in_quotes
+	})?.0;
+	let mut head = line[..colon].split(';');
+	let name = head.next()?.trim().to_ascii_uppercase();
+	let params = head
+		.filter_map(|p| p.split_once('='))
+		.map(|(k, v)| (k.trim().to_ascii_uppercase(), v.trim_matches('"').to_string()))
+		.collect();
+	Some((name, params, line[colon + 1..].to_string()))
+}
+
+fn parse_when(value: &str, tzid: Option<&str>) -> Result<When> {
+	let value = value.trim();
+	if value.len() == 8 {
+		return Ok(When::Date(NaiveDate::parse_from_str(value, "%Y%m%d").with_context(|| format!("invalid date: {}", value))?));
+	}
+	let (stamp, utc) = match value.strip_suffix('Z') {
+		Some(v) => (v, true),
+		None => (value, false),
+	};
+	let dt = NaiveDateTime::parse_from_str(stamp, "%Y%m%dT%H%M%S").with_context(|| format!("invalid date-time: {}", value))?;
+	Ok(match (utc, tzid.and_then(|z| z.parse::<Tz>().ok())) {
+		(true, _) => When::Utc(dt),
+		(false, Some(tz)) => When::Zoned(dt, tz),
+		// Unknown zone names (e.g. Windows ones) are read as local wall-clock time.
+		(false, None) => When::Floating(dt),
+	})
+}
+
+/// Parse an RFC 5545 duration such as `PT1H30M` or `P1D` into minutes.
+fn parse_duration(value: &str) -> Result<u32> {
+	let v = value.trim();
+	let body = v.strip_prefix('+').unwrap_or(v).strip_prefix('P').with_context(|| format!("invalid duration: {}", value))?;
+	let mut minutes = 0u32;
+	let mut num = String::new();
+	for c in body.chars() {
+		match c {
+			'0'..='9' => num.push(c),
+			'T' => {}
+			'W' | 'D' | 'H' | 'M' | 'S' => {
+				let n: u32 = num.parse().with_context(|| format!("invalid duration: {}", value))?;
+				num.clear();
+				minutes += match c { 'W' => n * 7 * 24 * 60, 'D' => n * 24 * 60, 'H' => n * 60, 'M' => n, _ => n / 60 };
+			}
+			_ => bail!("invalid duration: {}", value),
+		}
+	}
+	Ok(minutes)
+}
+
+fn unescape(s: &str) -> String {
+	let mut out = String::new();
+	let mut chars = s.chars();
+	while let Some(c) = chars.next() {
+		if c != '\\' { out.push(c); continue; }
+		match chars.next() {
+			Some('n') | Some('N') => out.push('\n'),
+			Some(other) => out.push(other),
+			None => {}
+		}
+	}
+	out
+}
+
+/// Split a comma-separated TEXT list, honouring `\,` escapes.
+fn split_text_list(s: &str) -> Vec<String> {
+	let mut items = vec![String::new()];
+	let mut escaped = false;
+	for c in s.chars() {
+		match c {
+			',' if !escaped => items.push(String::new()),
+			'\\' if !escaped => { escaped = true; continue; }
+			_ => items.last_mut().expect("at least one item").push(c),
+		}
+		escaped = false;
+	}
+	items.into_iter().map(|i| i.trim().to_string()).filter(|i| !i.is_empty()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bullet::parse_bullets;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_calendar_events_and_todos() {
+        let lines: Vec<String> = [
+            "- [ ] [mtg 10:00 30] Design review; round 2 #work #design ^k3x9",
+            "  - note: bring slides",
+            "  - note: book room",
+            "- [ ] (!!!) Ship release ^a1b2",
+            "- [x] Done already ^c3d4",
+            "- [>] [mtg 15:00 60] Moved away → 2025-11-07 ^e5f6",
+        ].iter().map(|s| s.to_string()).collect();
+        let date = NaiveDate::from_ymd_opt(2025, 11, 6).unwrap();
+        let hits: Vec<SearchHit> = parse_bullets(&lines).into_iter().map(|bullet| SearchHit { date, page: Page::Day, bullet }).collect();
+        let stamp = Utc.with_ymd_and_hms(2025, 11, 1, 8, 0, 0).unwrap();
+
+        let ics = calendar(&hits, true, stamp);
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n") && ics.ends_with("END:VCALENDAR\r\n"));
+        assert!(ics.contains("UID:k3x9-20251106@bullet-journal\r\n"), "UID should be stable: {}", ics);
+        assert!(ics.contains("DTSTART:20251106T100000\r\nDURATION:PT30M\r\n"));
+        assert!(ics.contains("SUMMARY:Design review\\; round 2\r\n"), "Text should be escaped");
+        assert!(ics.contains("CATEGORIES:work,design\r\n"));
+        assert!(ics.contains("DESCRIPTION:bring slides\\nbook room\r\n"));
+        assert!(ics.contains("BEGIN:VTODO\r\nUID:a1b2-20251106@bullet-journal\r\n"));
+        assert!(ics.contains("DUE;VALUE=DATE:20251106\r\nSTATUS:NEEDS-ACTION\r\nPRIORITY:1\r\n"));
+        assert!(!ics.contains("Done already") && !ics.contains("Moved away"), "Only live meetings and open tasks are exported");
+        assert_eq!(ics, calendar(&hits, true, stamp), "Output should be reproducible");
+        assert!(!calendar(&hits, false, stamp).contains("VTODO"), "Tasks are opt-in");
+    }
+
+    #[test]
+    fn test_fold_long_lines() {
+        let folded = fold(&format!("SUMMARY:{}", "é".repeat(60)));
+        for line in folded.split("\r\n").filter(|l| !l.is_empty()) {
+            assert!(line.len() <= 75, "Folded line too long: {}", line.len());
+        }
+        assert_eq!(folded.replace("\r\n ", ""), format!("SUMMARY:{}\r\n", "é".repeat(60)));
+    }
+
+    fn calendar_with(events: &str) -> String {
+        format!("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n{}END:VCALENDAR\r\n", events)
+    }
+
+    #[test]
+    fn test_import_events_and_recurrence() -> Result<()> {
+        let env = crate::test_support::TestEnv::new();
+        let j = &env.journal;
+        let d = |m, day| NaiveDate::from_ymd_opt(2025, m, day).unwrap();
+        let ics = calendar_with(concat!(
+            "BEGIN:VEVENT\r\nUID:one@example.com\r\nDTSTART:20251106T100000\r\nDTEND:20251106T104500\r\n",
+            "SUMMARY:Design review\\, round 2\r\nCATEGORIES:work,Big Project\r\n",
+            "DESCRIPTION:bring slides\\nbook room\r\n",
+            "BEGIN:VALARM\r\nSUMMARY:ignored\r\nEND:VALARM\r\nEND:VEVENT\r\n",
+            "BEGIN:VEVENT\r\nUID:standup@example.com\r\nDTSTART:20251103T093000\r\nDURATION:PT15M\r\n",
+            "RRULE:FREQ=WEEKLY;BYDAY=MO,WE\r\nEXDATE:20251105T093000\r\nSUMMARY:Standup\r\nEND:VEVENT\r\n",
+            "BEGIN:VEVENT\r\nUID:standup@example.com\r\nRECURRENCE-ID:20251110T093000\r\n",
+            "DTSTART:20251110T110000\r\nDURATION:PT15M\r\nSUMMARY:Standup (late)\r\nEND:VEVENT\r\n",
+            "BEGIN:VEVENT\r\nUID:holiday@example.com\r\nDTSTART;VALUE=DATE:20251111\r\nSUMMARY:Holiday\r\nEND:VEVENT\r\n",
+            "BEGIN:VEVENT\r\nUID:utc@example.com\r\nDTSTART:20251107T120000Z\r\nDURATION:PT1H\r\nSUMMARY:UTC call\r\nEND:VEVENT\r\n",
+        ));
+
+        let report = j.import_ics(&ics, d(11, 1), d(11, 12))?;
+        assert_eq!(report.added, 6, "1 meeting + 3 standups + holiday + UTC call: {:?}", report);
+
+        let review = &j.meetings(d(11, 6))?[0];
+        assert_eq!(review.text, "Design review, round 2");
+        assert_eq!(review.meeting_duration_min, Some(45), "DTEND should give the duration");
+        assert_eq!(review.tags, vec!["work", "Big-Project"]);
+        assert_eq!(review.notes, vec!["bring slides", "book room"]);
+
+        let standups: Vec<NaiveDate> = j.search(&crate::Query::parse("standup")?)?.iter().map(|h| h.date).collect();
+        assert_eq!(standups, vec![d(11, 3), d(11, 10), d(11, 12)], "EXDATE should be skipped");
+        assert_eq!(j.meetings(d(11, 10))?[0].meeting_time, NaiveTime::from_hms_opt(11, 0, 0), "Override should replace its occurrence");
+        assert_eq!(j.bullets(d(11, 11))?[0].kind, Kind::Event, "All-day events become events");
+        let utc = Utc.with_ymd_and_hms(2025, 11, 7, 12, 0, 0).unwrap().with_timezone(&Local).naive_local();
+        let call = j.search(&crate::Query::parse("utc")?)?.remove(0);
+        assert_eq!((call.date, call.bullet.meeting_time), (utc.date(), Some(utc.time())), "UTC times should be shown locally");
+
+        // Re-import: nothing new; a moved and a retimed event are updated in place
+        let again = j.import_ics(&ics, d(11, 1), d(11, 12))?;
+        assert_eq!((again.added, again.updated, again.unchanged), (0, 0, 6), "Re-import should not duplicate: {:?}", again);
+        let moved = ics.replace("DTSTART:20251106T100000\r\nDTEND:20251106T104500", "DTSTART:20251107T140000\r\nDTEND:20251107T150000")
+            .replace("DTSTART:20251110T110000", "DTSTART:20251110T113000");
+        let report = j.import_ics(&moved, d(11, 1), d(11, 12))?;
+        assert_eq!((report.added, report.updated), (0, 2), "{:?}", report);
+        assert!(j.meetings(d(11, 6))?.is_empty(), "Moved meeting should leave its old day");
+        let review = j.meetings(d(11, 7))?.into_iter().find(|b| b.text.starts_with("Design")).unwrap();
+        assert_eq!((review.meeting_time, review.meeting_duration_min), (NaiveTime::from_hms_opt(14, 0, 0), Some(60)));
+        assert_eq!(j.meetings(d(11, 10))?.len(), 1, "Retimed occurrence should not be duplicated");
+
+        // Cancellation in the source calendar cancels the bullet
+        let cancelled = moved.replace("SUMMARY:Holiday\r\n", "SUMMARY:Holiday\r\nSTATUS:CANCELLED\r\n")
+            .replace("SUMMARY:UTC call\r\n", "SUMMARY:UTC call\r\nSTATUS:CANCELLED\r\n");
+        assert_eq!(j.import_ics(&cancelled, d(11, 1), d(11, 12))?.cancelled, 2);
+        assert!(j.bullets(d(11, 11))?.is_empty(), "Cancelled all-day event should be removed");
+        assert_eq!(j.search(&crate::Query::parse("utc status:cancelled")?)?.len(), 1, "Cancelled meeting should be struck out");
+
+        // One import is one undo step
+        assert_eq!(j.undo()?.map(|op| op.label), Some("import".to_string()));
+        assert_eq!(j.bullets(d(11, 11))?.len(), 1, "Undo should restore the whole import");
+        assert_eq!(j.import_ics(&cancelled, d(11, 1), d(11, 12))?.cancelled, 2, "The UID list is rolled back with the bullets");
+        Ok(())
+    }
+
+    #[test]
+    fn test_imported_titles_are_plain_text() -> Result<()> {
+        let env = crate::test_support::TestEnv::new();
+        let j = &env.journal;
+        let date = NaiveDate::from_ymd_opt(2025, 11, 6).unwrap();
+        let ics = calendar_with(concat!(
+            "BEGIN:VEVENT\r\nUID:fix@example.com\r\nDTSTART:20251106T100000\r\nDURATION:PT30M\r\n",
+            "SUMMARY:(!!) Fix  #42 → 2025-01-01 ^abcd1234\r\nEND:VEVENT\r\n",
+        ));
+        j.import_ics(&ics, date, date)?;
+        let m = j.meetings(date)?.remove(0);
+        assert_eq!(m.text, "（!!) Fix ＃42 ⟶ 2025-01-01 ˆabcd1234", "Markers in the title should be defused");
+        assert_eq!((m.priority, m.tags.len(), m.migrated_to, m.id.as_deref() == Some("abcd1234")), (None, 0, None, false));
+        assert_eq!(j.import_ics(&ics, date, date)?.unchanged, 1, "Re-import should see the same title");
+        Ok(())
+    }
+
+    #[test]
+    fn test_import_window_and_bad_events() -> Result<()> {
+        let env = crate::test_support::TestEnv::new();
+        let j = &env.journal;
+        let d = |m, day| NaiveDate::from_ymd_opt(2025, m, day).unwrap();
+        let ics = calendar_with(concat!(
+            "BEGIN:VEVENT\r\nUID:in@example.com\r\nDTSTART:20251106T100000\r\nSUMMARY:Inside\r\nEND:VEVENT\r\n",
+            "BEGIN:VEVENT\r\nUID:out@example.com\r\nDTSTART:20251206T100000\r\nSUMMARY:Outside\r\nEND:VEVENT\r\n",
+            "BEGIN:VEVENT\r\nUID:bad@example.com\r\nDTSTART:20251107T100000\r\nDURATION:soon\r\nSUMMARY:Broken\r\nEND:VEVENT\r\n",
+            "BEGIN:VEVENT\r\nUID:weekly@example.com\r\nDTSTART:20251103T090000\r\nRRULE:FREQ=WEEKLY\r\nSUMMARY:Weekly\r\nEND:VEVENT\r\n",
+            "BEGIN:VEVENT\r\nUID:weekly@example.com\r\nRECURRENCE-ID:20251110T090000\r\nDTSTART:20251201T090000\r\nSUMMARY:Weekly\r\nEND:VEVENT\r\n",
+        ));
+        assert!(j.import_ics(&ics, d(11, 12), d(11, 1)).is_err(), "A reversed range should be refused");
+
+        let report = j.import_ics(&ics, d(11, 1), d(11, 12))?;
+        assert_eq!(report.added, 2, "Inside + the 3 Nov weekly; the 10th was moved out of the window: {:?}", report);
+        assert!(j.bullets(d(12, 6))?.is_empty() && j.bullets(d(11, 10))?.is_empty());
+        assert_eq!(report.warnings.len(), 1, "{:?}", report.warnings);
+        assert!(report.warnings[0].contains("Broken") && report.warnings[0].contains("invalid duration"), "{:?}", report.warnings);
+        Ok(())
+    }
+
+    #[test]
+    fn test_zoned_meetings() -> Result<()> {
+        let env = crate::test_support::TestEnv::new();
+        let j = &env.journal;
+        let date = NaiveDate::from_ymd_opt(2025, 11, 6).unwrap();
+        let ics = calendar_with(concat!(
+            "BEGIN:VEVENT\r\nUID:berlin@example.com\r\nDTSTART;TZID=Europe/Berlin:20251106T150000\r\n",
+            "DTEND;TZID=Europe/Berlin:20251106T153000\r\nSUMMARY:Berlin sync\r\nEND:VEVENT\r\n",
+        ));
+        j.import_ics(&ics, date, date)?;
+        let m = j.meetings(date)?.remove(0);
+        assert_eq!((m.meeting_time, m.meeting_duration_min, m.meeting_tz), (NaiveTime::from_hms_opt(15, 0, 0), Some(30), Some(chrono_tz::Europe::Berlin)), "TZID should be kept");
+        assert_eq!(j.import_ics(&ics, date, date)?.unchanged, 1, "Re-import should match the zoned bullet");
+
+        let out = j.export_ics(date, date, false)?;
+        assert!(out.contains("DTSTART:20251106T140000Z\r\nDURATION:PT30M\r\n"), "Zoned meetings are exported in UTC: {}", out);
+        Ok(())
+    }
+
+    #[test]
+    fn test_import_parsing_helpers() -> Result<()> {
+        assert_eq!(parse_duration("PT1H30M")?, 90);
+        assert_eq!(parse_duration("P1DT2H")?, 26 * 60);
+        assert!(parse_duration("1H").is_err());
+        let when = parse_when("20251106T100000", Some("Europe/Berlin"))?;
+        assert_eq!(when, When::Zoned(NaiveDate::from_ymd_opt(2025, 11, 6).unwrap().and_hms_opt(10, 0, 0).unwrap(), chrono_tz::Europe::Berlin));
+        assert!(matches!(parse_when("20251106T100000", Some("W. Europe Standard Time"))?, When::Floating(_)), "Unknown zones fall back to floating time");
+        assert_eq!(split_text_list("a\\,b,c"), vec!["a,b", "c"]);
+        assert!(parse_events("hello", &mut Vec::new()).is_err(), "Non-calendar input should be rejected");
+        Ok(())
+    }
+}
@@ -0,0 +1,1166 @@
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use chrono::{Datelike, Days, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta};
+use chrono_tz::Tz;
+use directories::ProjectDirs;
+
+use crate::bullet::{check_text, format_bullet_line, forwarded, ids_in, meeting_prefix, new_id, parse_bullets, to_local, with_state, Bullet, BulletRef, Kind, Link, State};
+use crate::clock::Clock;
+use crate::undo::Pending;
+
+/// Default location of the journal: the platform data dir for `bullet_journal`.
+pub fn default_dir() -> Result<PathBuf> {
+	let proj = ProjectDirs::from("dev", "local", "bullet_journal").context("cannot resolve project dirs")?;
+	Ok(proj.data_dir().to_path_buf())
+}
+
+/// A meeting as a local-time interval.
+#[derive(Debug, Clone)]
+pub struct Meeting {
+	/// Day file the meeting is written in; its local start may fall on another day.
+	pub date: NaiveDate,
+	pub bullet: Bullet,
+	pub start: NaiveDateTime,
+	pub end: NaiveDateTime,
+}
+
+/// How many days before a window to look for meetings still running in it.
+const MEETING_LOOKBACK_DAYS: u64 = 7;
+
+/// A meeting that falls inside the notification window.
+#[derive(Debug, Clone)]
+pub struct UpcomingMeeting {
+	pub date: NaiveDate,
+	pub bullet: Bullet,
+	pub minutes_until: i64,
+}
+
+/// What to do with an open bullet when reviewing a migration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decision {
+	/// Carry it forward to a day, leaving a `[>]` marker.
+	Forward(NaiveDate),
+	/// Schedule it for a future day, leaving a `[<]` marker.
+	Schedule(NaiveDate),
+	Done,
+	Cancel,
+	/// Leave it open where it is.
+	Keep,
+}
+
+/// A bullet journal stored as one Markdown file per day under `root`.
+#[derive(Debug, Clone)]
+pub struct Journal {
+	root: PathBuf,
+	clock: Clock,
+	/// Undo record of the operation in progress, if any.
+	pub(crate) pending: RefCell<Option<Pending>>,
+}
+
+impl Journal {
+	/// Open the journal rooted at `root`. The directory is created by the
+	/// first change, so reading a journal that doesn't exist yet leaves no trace.
+	pub fn open(root: impl Into<PathBuf>) -> Result<Self> {
+		Ok(Journal { root: root.into(), clock: Clock::system(), pending: RefCell::new(None) })
+	}
+
+	/// Open the journal in [`default_dir`].
+	pub fn open_default() -> Result<Self> {
+		Self::open(default_dir()?)
+	}
+
+	pub fn root(&self) -> &Path {
+		&self.root
+	}
+
+	/// The same journal, with "now" and "today" read from `clock`.
+	pub fn with_clock(mut self, clock: Clock) -> Self {
+		self.clock = clock;
+		self
+	}
+
+	pub fn clock(&self) -> Clock {
+		self.clock
+	}
+
+	/// Take the journal's advisory lock, held until the returned file is
+	/// dropped. Read-modify-write cycles run under it so concurrent `bj`
+	/// processes (say, a notify timer and an interactive add) don't interleave.
+	pub(crate) fn lock(&self) -> Result<File> {
+		fs::create_dir_all(&self.root).with_context(|| format!("create data dir {}", self.root.display()))?;
+		let path = self.root.join(".lock");
+		let f = OpenOptions::new().create(true).truncate(false).write(true).open(&path).with_context(|| format!("open {}", path.display()))?;
+		f.lock().with_context(|| format!("lock {}", path.display()))?;
+		Ok(f)
+	}
+
+	pub fn file_for(&self, date: NaiveDate) -> PathBuf {
+		let fname = format!("{}-{:02}-{:02}.md", date.year(), date.month(), date.day());
+		self.root.join(fname)
+	}
+
+	/// Every date that has a day file, oldest first.
+	pub fn days(&self) -> Result<Vec<NaiveDate>> {
+		let mut days = Vec::new();
+		if !self.root.exists() { return Ok(days); }
+		for entry in fs::read_dir(&self.root).with_context(|| format!("read {}", self.root.display()))? {
+			let name = entry?.file_name();
+			let Some(stem) = name.to_str().and_then(|n| n.strip_suffix(".md")) else { continue };
+			if let Ok(d) = NaiveDate::parse_from_str(stem, "%Y-%m-%d") { days.push(d); }
+		}
+		days.sort();
+		Ok(days)
+	}
+
+	/// All bullets for a date in file order, followed by recurring
+	/// occurrences that have not been written to the file yet.
+	pub fn bullets(&self, date: NaiveDate) -> Result<Vec<Bullet>> {
+		let lines = read_file_lines(&self.file_for(date))?;
+		let mut bullets = parse_bullets(&lines);
+		self.add_occurrences(date, &lines, &mut bullets)?;
+		Ok(bullets)
+	}
+
+	/// Meetings for a date, sorted by local start time.
+	pub fn meetings(&self, date: NaiveDate) -> Result<Vec<Bullet>> {
+		let mut bullets = self.bullets(date)?
+			.into_iter()
+			.filter(|b| b.meeting_time.is_some())
+			.collect::<Vec<_>>();
+		bullets.sort_by_key(|b| b.meeting_start(date));
+		Ok(bullets)
+	}
+
+	/// Meetings overlapping `from..to` in local time, sorted by start. Meetings
+	/// that started up to a week earlier and are still running are included,
+	/// as are meetings whose zone moves them onto a neighbouring day.
+	/// Migrated and scheduled lines are skipped; the live copy is found on its own day.
+	pub fn meetings_between(&self, from: NaiveDateTime, to: NaiveDateTime) -> Result<Vec<Meeting>> {
+		let first = from.date() - Days::new(MEETING_LOOKBACK_DAYS);
+		let last = to.date() + Days::new(1);
+		let mut out = Vec::new();
+		for date in first.iter_days().take_while(|d| *d <= last) {
+			for bullet in self.bullets(date)? {
+				if matches!(bullet.state, State::Migrated | State::Scheduled) { continue; }
+				let (Some(start), Some(end)) = (bullet.meeting_start(date), bullet.meeting_end(date)) else { continue };
+				// Zero-length meetings still count at their start.
+				if start < to && (end > from || start >= from) {
+					out.push(Meeting { date, bullet, start, end });
+				}
+			}
+		}
+		out.sort_by_key(|m| m.start);
+		Ok(out)
+	}
+
+	/// Meetings on the journal that would overlap a new meeting at `time` on
+	/// `date`. Cancelled meetings are ignored.
+	pub fn meeting_conflicts(&self, date: NaiveDate, time: NaiveTime, duration_min: u32, tz: Option<Tz>) -> Result<Vec<Meeting>> {
+		let start = match tz {
+			Some(tz) => to_local(date.and_time(time), tz),
+			None => date.and_time(time),
+		};
+		let end = start + TimeDelta::minutes(duration_min.max(1).into());
+		Ok(self.meetings_between(start, end)?.into_iter().filter(|m| m.bullet.state != State::Cancelled && m.end > start).collect())
+	}
+
+	/// Gaps of at least `min_minutes` between meetings on `date` within
+	/// `from..to` local time. Cancelled meetings don't block time.
+	pub fn free_slots(&self, date: NaiveDate, from: NaiveTime, to: NaiveTime, min_minutes: u32) -> Result<Vec<(NaiveDateTime, NaiveDateTime)>> {
+		if to <= from { bail!("{} is not after {}", to.format("%H:%M"), from.format("%H:%M")); }
+		let (from, to) = (date.and_time(from), date.and_time(to));
+		let mut slots = Vec::new();
+		let mut cursor = from;
+		for m in self.meetings_between(from, to)? {
+			if m.bullet.state == State::Cancelled { continue; }
+			if m.start > cursor { slots.push((cursor, m.start.min(to))); }
+			cursor = cursor.max(m.end);
+		}
+		if cursor < to { slots.push((cursor, to)); }
+		slots.retain(|(a, b)| (*b - *a).num_minutes() >= i64::from(min_minutes));
+		Ok(slots)
+	}
+
+	/// Append an open task to a date under a fresh ID and return it.
+	pub fn add_bullet(&self, date: NaiveDate, text: &str, priority: Option<u8>, tags: &[String], notes: &[String]) -> Result<Bullet> {
+		self.add_entry(date, Kind::Task, text, priority, tags, notes)
+	}
+
+	/// Append a task, event or note to a date under a fresh ID and return it.
+	pub fn add_entry(&self, date: NaiveDate, kind: Kind, text: &str, priority: Option<u8>, tags: &[String], notes: &[String]) -> Result<Bullet> {
+		self.add_to(&self.file_for(date), kind, text, priority, tags, notes)
+	}
+
+	/// [`Journal::add_entry`] for any journal page.
+	pub(crate) fn add_to(&self, path: &Path, kind: Kind, text: &str, priority: Option<u8>, tags: &[String], notes: &[String]) -> Result<Bullet> {
+		check_text(text)?;
+		self.operation("add", || {
+			let id = new_id(&taken_ids(path)?);
+			let mut block = vec![format_bullet_line(kind, text, priority, tags, &id)];
+			block.extend(notes.iter().map(|n| format!("  - note: {}", n)));
+			self.append_to(path, block)
+		})
+	}
+
+	/// Append a bullet line followed by its notes and sub-content to a day
+	/// file and return the bullet.
+	pub(crate) fn append_bullet(&self, date: NaiveDate, block: Vec<String>) -> Result<Bullet> {
+		self.append_to(&self.file_for(date), block)
+	}
+
+	/// [`Journal::append_bullet`] for any journal page.
+	pub(crate) fn append_to(&self, path: &Path, block: Vec<String>) -> Result<Bullet> {
+		let mut lines = read_file_lines(path)?;
+		let start = lines.len();
+		lines.extend(block);
+		self.write_lines(path, &lines)?;
+		Ok(parse_bullets(&lines).into_iter().find(|b| b.line_index == start).expect("bullet just written"))
+	}
+
+	/// ID to carry over when `b` moves from `from_lines` to the page at `to`;
+	/// legacy bullets get a new one, unused on either page.
+	fn carried_id(&self, b: &Bullet, from_lines: &[String], to: &Path) -> Result<String> {
+		match &b.id {
+			Some(id) => Ok(id.clone()),
+			None => {
+				let mut taken = ids_in(from_lines);
+				taken.extend(taken_ids(to)?);
+				Ok(new_id(&taken))
+			}
+		}
+	}
+
+	/// Append a meeting starting at `time`, in zone `tz` or local time when `None`.
+	#[allow(clippy::too_many_arguments)]
+	pub fn add_meeting(&self, date: NaiveDate, time: NaiveTime, duration_min: u32, tz: Option<Tz>, title: &str, tags: &[String], notes: &[String]) -> Result<Bullet> {
+		let full = format!("{}{}", meeting_prefix(time, Some(duration_min), tz), title);
+		self.add_bullet(date, &full, None, tags, notes)
+	}
+
+	/// Mark task `id` on `date` done and return it.
+	pub fn mark_done(&self, date: NaiveDate, id: impl Into<BulletRef>) -> Result<Bullet> {
+		self.set_state(date, id.into(), State::Done)
+	}
+
+	/// Strike task `id` on `date` out as no longer relevant and return it.
+	pub fn cancel_bullet(&self, date: NaiveDate, id: impl Into<BulletRef>) -> Result<Bullet> {
+		self.set_state(date, id.into(), State::Cancelled)
+	}
+
+	fn set_state(&self, date: NaiveDate, id: BulletRef, state: State) -> Result<Bullet> {
+		self.operation(state.as_str(), || {
+			self.materialize(date)?;
+			let path = self.file_for(date);
+			let mut lines = read_file_lines(&path)?;
+			let bullets = parse_bullets(&lines);
+			let Some(target) = bullets.into_iter().find(|b| id.matches(b)) else { bail!("bullet {} not found", id) };
+			if target.kind != Kind::Task { bail!("bullet {} is not a task", id); }
+			if target.state != State::Open && target.state != state { bail!("bullet {} is not open", id); }
+			lines[target.line_index] = with_state(&lines[target.line_index], &target, state);
+			self.write_lines(&path, &lines)?;
+			Ok(Bullet { state, ..target })
+		})
+	}
+
+	/// Remove bullet `id` on `date` together with its note lines and return it.
+	pub fn delete_bullet(&self, date: NaiveDate, id: impl Into<BulletRef>) -> Result<Bullet> {
+		self.operation("delete", || {
+			let id = id.into();
+			self.materialize(date)?;
+			let path = self.file_for(date);
+			let mut lines = read_file_lines(&path)?;
+			let bullets = parse_bullets(&lines);
+			let Some(target) = bullets.into_iter().find(|b| id.matches(b)) else { bail!("bullet {} not found", id) };
+			let end = block_end(&lines, target.line_index);
+			lines.drain(target.line_index..end);
+			self.write_lines(&path, &lines)?;
+			self.suppress_occurrence(date, &target)?;
+			Ok(target)
+		})
+	}
+
+	/// Rewrite bullet `id` on `date` in place through `edit` and return the
+	/// result. Position, indentation and everything below the notes are kept.
+	pub fn update_bullet(&self, date: NaiveDate, id: impl Into<BulletRef>, edit: impl FnOnce(&mut Bullet)) -> Result<Bullet> {
+		self.operation("edit", || {
+			let id = id.into();
+			self.materialize(date)?;
+			let path = self.file_for(date);
+			let mut lines = read_file_lines(&path)?;
+			let bullets = parse_bullets(&lines);
+			let Some(target) = bullets.into_iter().find(|b| id.matches(b)) else { bail!("bullet {} not found", id) };
+			let mut updated = target.clone();
+			edit(&mut updated);
+			if updated.text.trim().is_empty() { bail!("bullet text cannot be empty"); }
+			if updated.text != target.text { check_text(&updated.text)?; }
+			if updated.kind != Kind::Task && updated.state != State::Open { bail!("only tasks can be {}", updated.state.as_str()); }
+			let line = &lines[target.line_index];
+			let indent = &line[..line.len() - line.trim_start().len()];
+			let mut block = updated.block();
+			block[0] = format!("{}{}", indent, block[0]);
+			let notes_end = target.line_index + 1 + target.notes.len();
+			lines.splice(target.line_index..notes_end, block);
+			self.write_lines(&path, &lines)?;
+			Ok(updated)
+		})
+	}
+
+	/// Replace bullet `id` on `date` with the single bullet parsed from
+	/// `block`, an edited copy of [`Bullet::block`]. The bullet keeps its ID
+	/// and position.
+	pub fn replace_bullet(&self, date: NaiveDate, id: impl Into<BulletRef>, block: &[String]) -> Result<Bullet> {
+		let mut parsed = parse_bullets(block);
+		if parsed.len() != 1 { bail!("expected exactly one bullet, found {}", parsed.len()); }
+		let edited = parsed.remove(0);
+		self.update_bullet(date, id, |b| *b = Bullet { line_index: b.line_index, visible_index: b.visible_index, id: b.id.clone(), ..edited })
+	}
+
+	/// Migrate `b` (at `from_lines[b.line_index]`) to the page at `to`: the
+	/// destination gets an open copy recording where it came from, and the
+	/// source line becomes a `[>]` (or `[<]` for `state` Scheduled) marker
+	/// pointing at the destination. `from` and `to_link` name the two pages.
+	/// Notes and sub-content move with the bullet; returns how many lines
+	/// left `from_lines`.
+	pub(crate) fn forward(&self, from: Link, from_lines: &mut Vec<String>, b: &Bullet, to: &Path, to_link: Link, state: State) -> Result<usize> {
+		let carried = self.carried_id(b, from_lines, to)?;
+		let (migrated_from, migrated_from_month, migrated_from_collection) = Link::split(Some(from));
+		let arrival = Bullet {
+			state: State::Open,
+			id: Some(carried.clone()),
+			migrated_from,
+			migrated_from_month,
+			migrated_from_collection,
+			migrated_to: None,
+			migrated_to_month: None,
+			migrated_to_collection: None,
+			// Moved off its day, an occurrence is an ordinary bullet.
+			recurring: false,
+			series: None,
+			..b.clone()
+		};
+		let end = block_end(from_lines, b.line_index);
+		let mut block = vec![arrival.to_line()];
+		block.extend(from_lines.drain(b.line_index + 1..end));
+		let moved = block.len() - 1;
+		self.append_to(to, block)?;
+		from_lines[b.line_index] = forwarded(&from_lines[b.line_index], b, state, Some(&to_link), &carried);
+		Ok(moved)
+	}
+
+	/// Migrate open bullet `id` from one date to another and return it.
+	pub fn migrate_one(&self, from: NaiveDate, to: NaiveDate, id: impl Into<BulletRef>) -> Result<Bullet> {
+		self.operation("migrate", || {
+			let id = id.into();
+			if from == to { bail!("from and to dates are the same; nothing to migrate"); }
+			self.materialize(from)?;
+			let from_path = self.file_for(from);
+			let mut from_lines = read_file_lines(&from_path)?;
+			let bullets = parse_bullets(&from_lines);
+			let Some(target) = bullets.into_iter().find(|b| id.matches(b)) else { bail!("bullet {} not found on {}", id, from) };
+			if target.is_done() { bail!("bullet {} is already completed", id); }
+			if !target.is_open() { bail!("bullet {} is not an open task", id); }
+			self.forward(Link::Day(from), &mut from_lines, &target, &self.file_for(to), Link::Day(to), State::Migrated)?;
+			self.write_lines(&from_path, &from_lines)?;
+			self.suppress_occurrence(from, &target)?;
+			Ok(target)
+		})
+	}
+
+	pub fn migrate_one_to_today(&self, from: NaiveDate, id: impl Into<BulletRef>) -> Result<Bullet> {
+		self.migrate_one(from, self.clock.today(), id)
+	}
+
+	/// Open bullets a migration of `from` walks through, in file order.
+	/// Recurring occurrences stay on their own day, and open sub-tasks travel
+	/// inside their parent's block rather than on their own.
+	pub fn migration_candidates(&self, from: NaiveDate) -> Result<Vec<Bullet>> {
+		let lines = read_file_lines(&self.file_for(from))?;
+		let series = self.recurring_ids()?;
+		let mut covered = 0;
+		let mut open = Vec::new();
+		for b in parse_bullets(&lines) {
+			if b.line_index < covered || !b.is_open() || b.recurring || b.id.as_ref().is_some_and(|id| series.contains(id)) { continue; }
+			covered = block_end(&lines, b.line_index);
+			open.push(b);
+		}
+		Ok(open)
+	}
+
+	/// Migrate every open bullet from one date to another and return the
+	/// moved bullets.
+	pub fn migrate_open(&self, from: NaiveDate, to: NaiveDate) -> Result<Vec<Bullet>> {
+		if from == to { bail!("from and to dates are the same; nothing to migrate"); }
+		let decisions = self.migration_candidates(from)?
+			.into_iter()
+			.map(|b| (BulletRef::from(b.visible_index), Decision::Forward(to)))
+			.collect();
+		Ok(self.apply_migration(from, decisions)?.into_iter().map(|(b, _)| b).collect())
+	}
+
+	/// Apply a reviewed migration of `from` in one pass. Every decision is
+	/// checked against the day's migration candidates before anything is
+	/// written; returns the affected bullets in file order.
+	pub fn apply_migration(&self, from: NaiveDate, decisions: Vec<(BulletRef, Decision)>) -> Result<Vec<(Bullet, Decision)>> {
+		self.operation("migrate", || {
+			let candidates = self.migration_candidates(from)?;
+			let mut plan: Vec<(Bullet, Decision)> = Vec::new();
+			for (id, decision) in decisions {
+				let Some(b) = candidates.iter().find(|b| id.matches(b)) else { bail!("bullet {} is not an open task on {}", id, from) };
+				if plan.iter().any(|(p, _)| p.line_index == b.line_index) { bail!("bullet {} has more than one decision", id); }
+				if let Decision::Forward(to) | Decision::Schedule(to) = decision {
+					if to == from { bail!("cannot move bullet {} to the day it is already on", id); }
+				}
+				plan.push((b.clone(), decision));
+			}
+			plan.sort_by_key(|(b, _)| b.line_index);
+
+			let from_path = self.file_for(from);
+			let mut from_lines = read_file_lines(&from_path)?;
+			let mut removed = 0;
+			for (b, decision) in &plan {
+				let at = Bullet { line_index: b.line_index - removed, ..b.clone() };
+				match *decision {
+					Decision::Forward(to) => removed += self.forward(Link::Day(from), &mut from_lines, &at, &self.file_for(to), Link::Day(to), State::Migrated)?,
+					Decision::Schedule(to) => removed += self.forward(Link::Day(from), &mut from_lines, &at, &self.file_for(to), Link::Day(to), State::Scheduled)?,
+					Decision::Done => from_lines[at.line_index] = with_state(&from_lines[at.line_index], &at, State::Done),
+					Decision::Cancel => from_lines[at.line_index] = with_state(&from_lines[at.line_index], &at, State::Cancelled),
+					Decision::Keep => {}
+				}
+			}
+			self.write_lines(&from_path, &from_lines)?;
+			Ok(plan)
+		})
+	}
+
+	pub fn migrate_open_to_today(&self, from: NaiveDate) -> Result<Vec<Bullet>> {
+		self.migrate_open(from, self.clock.today())
+	}
+
+	fn notified_state_path(&self) -> PathBuf { self.root.join("notified.meetings") }
+
+	/// Meetings starting within `window_minutes`, including ones just past
+	/// midnight, that have not been announced yet. Returned meetings are recorded so they are only reported once.
+	pub fn take_upcoming_meetings(&self, window_minutes: i64) -> Result<Vec<UpcomingMeeting>> {
+		let now = self.clock.now();
+		self.operation("notify", || {
+			let state_path = self.notified_state_path();
+			let mut sent: Vec<String> = Vec::new();
+			if state_path.exists() {
+				let s = fs::read_to_string(&state_path).unwrap_or_default();
+				sent.extend(s.lines().map(|l| l.to_string()));
+			}
+			let known: HashSet<String> = sent.iter().cloned().collect();
+			let mut due = Vec::new();
+			let until = now + TimeDelta::minutes(window_minutes);
+			for m in self.meetings_between(now, until + TimeDelta::minutes(1))? {
+				let Some(t) = m.bullet.meeting_time else { continue };
+				if m.bullet.state == State::Cancelled { continue; }
+				let start_key = format!("{}|{}", m.date, t.format("%H:%M"));
+				if known.contains(&start_key) { continue; }
+				let diff = (m.start - now).num_minutes();
+				if m.start >= now && (0..=window_minutes).contains(&diff) {
+					sent.push(start_key);
+					due.push(UpcomingMeeting { date: m.date, bullet: m.bullet, minutes_until: diff });
+				}
+			}
+			if !due.is_empty() {
+				write_file_lines(&state_path, &sent).ok();
+			}
+			Ok(due)
+		})
+	}
+}
+
+/// Index one past the last line belonging to the bullet at `start`: its
+/// indented note lines and any blank lines between them.
+pub(crate) fn block_end(lines: &[String], start: usize) -> usize {
+	let indent = |l: &str| l.len() - l.trim_start().len();
+	let base = indent(&lines[start]);
+	let mut end = start + 1;
+	for (i, line) in lines.iter().enumerate().skip(start + 1) {
+		if line.trim().is_empty() {
+			// Empty line might separate bullets, keep looking
+			continue;
+		} else if line.starts_with("  - note: ") || indent(line) > base {
+			// A note, or sub-content indented under the bullet
+			end = i + 1;
+		} else {
+			// Hit the next bullet or non-note content
+			break;
+		}
+	}
+	end
+}
+
+/// IDs already used on the page at `path`, for minting a new one there.
+fn taken_ids(path: &Path) -> Result<HashSet<String>> {
+	Ok(ids_in(&read_file_lines(path)?))
+}
+
+pub(crate) fn read_file_lines(path: &Path) -> Result<Vec<String>> {
+	if !path.exists() {
+		return Ok(vec![]);
+	}
+	let mut f = OpenOptions::new().read(true).open(path).with_context(|| format!("open {}", path.display()))?;
+	let mut s = String::new();
+	f.read_to_string(&mut s).with_context(|| format!("read {}", path.display()))?;
+	Ok(s.lines().map(|l| l.to_string()).collect())
+}
+
+pub(crate) fn write_file_lines(path: &Path, lines: &[String]) -> Result<()> {
+	let contents = if lines.is_empty() { String::new() } else { format!("{}\n", lines.join("\n")) };
+	write_atomic(path, contents.as_bytes())
+}
+
+/// Replace `path` with `contents` so readers and crashes see either the old
+/// or the new file: write a temp file beside it, fsync, then rename over it.
+pub fn write_atomic(path: &Path, contents: &[u8]) -> Result<()> {
+	let name = path.file_name().with_context(|| format!("not a file: {}", path.display()))?;
+	let tmp = path.with_file_name(format!(".{}.tmp", name.to_string_lossy()));
+	let mut f = File::create(&tmp).with_context(|| format!("write {}", tmp.display()))?;
+	f.write_all(contents).and_then(|_| f.sync_all()).with_context(|| format!("write {}", tmp.display()))?;
+	fs::rename(&tmp, path).with_context(|| format!("write {}", path.display()))?;
+	// Make the rename itself durable; directories can't be opened on every platform.
+	if let Some(dir) = path.parent().and_then(|d| File::open(d).ok()) { dir.sync_all().ok(); }
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+    use crate::test_support::TestEnv;
+
+    #[test]
+    #[serial]  // Prevent parallel test runs
+    fn test_meeting_metadata() -> Result<()> {
+        let env = TestEnv::new();
+        let j = &env.journal;
+        let date = NaiveDate::from_ymd_opt(2025, 11, 6).unwrap();
+
+        // Add a meeting
+        j.add_meeting(date,
+            NaiveTime::from_hms_opt(14, 30, 0).unwrap(),
+            45,
+            None,
+            "Team Sync",
+            &["work".to_string()],
+            &["Prep required".to_string()]
+        )?;
+
+        // Verify the meeting was added correctly
+        let bullets = j.bullets(date)?;
+        assert_eq!(bullets.len(), 1, "Expected exactly one meeting bullet");
+
+        let mtg = &bullets[0];
+        assert_eq!(mtg.text, "Team Sync", "Meeting title mismatch");
+        assert_eq!(mtg.meeting_time.unwrap().format("%H:%M").to_string(), "14:30", "Meeting time mismatch");
+        assert_eq!(mtg.meeting_duration_min, Some(45), "Meeting duration mismatch");
+        assert_eq!(mtg.tags, vec!["work"], "Meeting tag mismatch");
+        assert_eq!(mtg.notes, vec!["Prep required"], "Meeting note mismatch");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_every_kind_and_state_round_trips() -> Result<()> {
+        let env = TestEnv::new();
+        let j = &env.journal;
+        let date = NaiveDate::from_ymd_opt(2025, 11, 6).unwrap();
+        j.add_bullet(date, "Thing", None, &[], &[])?;
+        for kind in [Kind::Task, Kind::Event, Kind::Note] {
+            for state in [State::Open, State::Done, State::Migrated, State::Scheduled, State::Cancelled] {
+                let written = j.update_bullet(date, 1, |b| { b.kind = kind; b.state = state; });
+                if kind != Kind::Task && state != State::Open {
+                    assert!(written.is_err(), "{:?} can't be {:?}", kind, state);
+                    continue;
+                }
+                let back = &j.bullets(date)?[0];
+                assert_eq!((back.kind, back.state, back.text.as_str()), (kind, state, "Thing"), "{:?} {:?} should survive a save", kind, state);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_reading_creates_nothing() -> Result<()> {
+        let env = TestEnv::new();
+        let j = Journal::open(env.journal.root().join("typo"))?;
+        let date = NaiveDate::from_ymd_opt(2025, 11, 6).unwrap();
+        assert!(j.bullets(date)?.is_empty() && j.days()?.is_empty() && j.months()?.is_empty() && j.collections()?.is_empty());
+        assert!(!j.root().exists(), "Reads must not create the journal");
+        j.add_bullet(date, "First", None, &[], &[])?;
+        assert!(j.file_for(date).exists(), "The first change creates it");
+        Ok(())
+    }
+
+    #[test]
+    #[serial]  // Prevent parallel test runs
+    fn test_add_and_parse_bullet() -> Result<()> {
+        let env = TestEnv::new();
+        let j = &env.journal;
+        let date = NaiveDate::from_ymd_opt(2025, 11, 6).unwrap();
+
+        // Add a bullet with priority, tags, and notes
+        j.add_bullet(date, "Write tests", Some(2), &["dev".to_string()], &["first note".to_string()])?;
+
+        let bullets = j.bullets(date)?;
+
+        assert_eq!(bullets.len(), 1, "Expected exactly one bullet");
+        let b = &bullets[0];
+        assert_eq!(b.text, "Write tests", "Bullet text mismatch");
+        assert_eq!(b.priority, Some(2), "Priority mismatch");
+        assert_eq!(b.tags, vec!["dev"], "Tags mismatch");
+        assert_eq!(b.notes.len(), 1, "Expected one note");
+        assert!(b.notes[0].contains("first note"), "Note content mismatch");
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]  // Prevent parallel test runs
+    fn test_mark_done() -> Result<()> {
+        let env = TestEnv::new();
+        let j = &env.journal;
+        let date = NaiveDate::from_ymd_opt(2025, 11, 6).unwrap();
+
+        // Add two bullets
+        j.add_bullet(date, "Task A", None, &[], &[])?;
+        j.add_bullet(date, "Task B", None, &[], &[])?;
+
+        // Parse to verify initial state
+        let initial = j.bullets(date)?;
+        assert_eq!(initial.len(), 2, "Expected two bullets initially");
+        assert!(!initial[0].is_done() && !initial[1].is_done(), "Bullets should start incomplete");
+
+        // Mark first one done
+        let done = j.mark_done(date, 1)?;
+        assert_eq!(done.text, "Task A", "Returned bullet should be the one marked");
+
+        let bullets = j.bullets(date)?;
+        assert_eq!(bullets.len(), 2, "Should still have two bullets after marking one done");
+        assert!(bullets[0].is_done(), "First bullet should be marked done");
+        assert!(!bullets[1].is_done(), "Second bullet should still be incomplete");
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]  // Prevent parallel test runs
+    fn test_migrate_one_to_today() -> Result<()> {
+        let today = NaiveDate::from_ymd_opt(2025, 11, 5).unwrap();
+        let env = TestEnv::at(today.and_hms_opt(9, 0, 0).unwrap());
+        let j = &env.journal;
+        let from = NaiveDate::from_ymd_opt(2025, 11, 4).unwrap();
+
+        // Create two bullets on source date with unique identifiable text
+        j.add_bullet(from, "Source Bullet A", None, &[], &[])?;
+        j.add_bullet(from, "Source Bullet B", Some(2), &["important".to_string()], &[])?;
+
+        // Read source file to find bullet indices
+        let initial = j.bullets(from)?;
+        assert_eq!(initial.len(), 2, "Should have two bullets initially");
+
+        // Find B's index and migrate it
+        let b_index = initial.iter()
+            .find(|b| b.text == "Source Bullet B")
+            .map(|b| b.visible_index)
+            .expect("Should find bullet B");
+        j.migrate_one_to_today(from, b_index)?;
+
+        // Verify source file - bullet A stays open, bullet B becomes a forwarding marker
+        let source_after = j.bullets(from)?;
+        assert_eq!(source_after.iter().filter(|b| b.is_open()).count(), 1, "Source should have one open bullet remaining");
+        assert_eq!(source_after[0].text, "Source Bullet A", "Wrong bullet migrated from source");
+        assert_eq!(source_after[1].state, State::Migrated, "Migrated bullet should be marked [>]");
+        assert_eq!(source_after[1].migrated_to, Some(today), "Forwarding marker should point at today");
+
+        // Verify target file - should have bullet B with metadata
+        let target = j.bullets(today)?;
+        assert_eq!(target.len(), 1, "Target should have one bullet");
+
+        let migrated = &target[0];
+        assert_eq!(migrated.text, "Source Bullet B", "Wrong bullet migrated");
+        assert_eq!(migrated.priority, Some(2), "Priority not preserved");
+        assert_eq!(migrated.tags, vec!["important"], "Tags not preserved");
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]  // Prevent parallel test runs
+    fn test_migrate_open_to_today() -> Result<()> {
+        let today = NaiveDate::from_ymd_opt(2025, 11, 5).unwrap();
+        let env = TestEnv::at(today.and_hms_opt(9, 0, 0).unwrap());
+        let j = &env.journal;
+        let from = NaiveDate::from_ymd_opt(2025, 11, 3).unwrap();
+
+        // Add three bullets with unique identifiable text
+        j.add_bullet(from, "First Task (Done)", None, &[], &[])?;
+        j.add_bullet(from, "Second Task (Open)", Some(1), &["tag1".to_string()], &[])?;
+        j.add_bullet(from, "Third Task (Open)", Some(3), &["tag2".to_string()], &[])?;
+
+        // Mark first task done
+        let initial = j.bullets(from)?;
+        let first_id = initial.iter()
+            .find(|b| b.text == "First Task (Done)")
+            .map(|b| b.visible_index)
+            .expect("Should find first task");
+        j.mark_done(from, first_id)?;
+
+        // Migrate open tasks
+        j.migrate_open_to_today(from)?;
+
+        // Verify source - done task stays, open tasks become forwarding markers
+        let source_after = j.bullets(from)?;
+        assert_eq!(source_after.len(), 3, "Source should keep all three bullets");
+        assert!(source_after[0].is_done(), "Source bullet should be done");
+        assert_eq!(source_after[0].text, "First Task (Done)", "Wrong task in source");
+        assert!(source_after[1..].iter().all(|b| b.state == State::Migrated), "Open tasks should be marked migrated");
+
+        // Verify target - should have both open tasks
+        let target = j.bullets(today)?;
+        assert_eq!(target.len(), 2, "Target should have two bullets");
+
+        let second = target.iter()
+            .find(|b| b.text == "Second Task (Open)")
+            .expect("Second task should be migrated");
+        assert_eq!(second.priority, Some(1), "Priority not preserved");
+        assert_eq!(second.tags, vec!["tag1"], "Tags not preserved");
+
+        let third = target.iter()
+            .find(|b| b.text == "Third Task (Open)")
+            .expect("Third task should be migrated");
+        assert_eq!(third.priority, Some(3), "Priority not preserved");
+        assert_eq!(third.tags, vec!["tag2"], "Tags not preserved");
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]  // Prevent parallel test runs
+    fn test_delete_bullet() -> Result<()> {
+        let env = TestEnv::new();
+        let j = &env.journal;
+        let date = NaiveDate::from_ymd_opt(2025, 11, 6).unwrap();
+
+        // Add three bullets
+        j.add_bullet(date, "Task A", None, &[], &[])?;
+        j.add_bullet(date, "Task B", Some(2), &["important".to_string()], &["Note 1".to_string(), "Note 2".to_string()])?;
+        j.add_bullet(date, "Task C", None, &[], &[])?;
+
+        // Verify initial state
+        let initial = j.bullets(date)?;
+        assert_eq!(initial.len(), 3, "Expected three bullets initially");
+
+        // Find Task B's ID and delete it
+        let b_id = initial.iter()
+            .find(|b| b.text == "Task B")
+            .map(|b| b.visible_index)
+            .expect("Should find Task B");
+        j.delete_bullet(date, b_id)?;
+
+        // Verify deletion
+        let after = j.bullets(date)?;
+        assert_eq!(after.len(), 2, "Should have two bullets after deletion");
+
+        // Verify Task B is gone and others remain
+        assert!(after.iter().any(|b| b.text == "Task A"), "Task A should remain");
+        assert!(after.iter().any(|b| b.text == "Task C"), "Task C should remain");
+        assert!(!after.iter().any(|b| b.text == "Task B"), "Task B should be deleted");
+
+        // Verify visible indices are renumbered correctly
+        assert_eq!(after[0].visible_index, 1, "First bullet should be index 1");
+        assert_eq!(after[1].visible_index, 2, "Second bullet should be index 2");
+
+        // Task B's notes go with it
+        let lines = read_file_lines(&j.file_for(date))?;
+        assert!(!lines.iter().any(|l| l.contains("Note 1")), "Deleted bullet's notes should be removed");
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]  // Prevent parallel test runs
+    fn test_delete_meeting() -> Result<()> {
+        let env = TestEnv::new();
+        let j = &env.journal;
+        let date = NaiveDate::from_ymd_opt(2025, 11, 6).unwrap();
+
+        // Add a regular bullet and a meeting
+        j.add_bullet(date, "Regular Task", None, &[], &[])?;
+        j.add_meeting(date,
+            NaiveTime::from_hms_opt(14, 30, 0).unwrap(),
+            45,
+            None,
+            "Team Sync",
+            &["work".to_string()],
+            &["Prep agenda".to_string()]
+        )?;
+
+        // Verify initial state
+        let initial = j.bullets(date)?;
+        assert_eq!(initial.len(), 2, "Expected two bullets initially");
+
+        // Find and delete the meeting
+        let mtg_id = initial.iter()
+            .find(|b| b.text == "Team Sync")
+            .map(|b| b.visible_index)
+            .expect("Should find meeting");
+        j.delete_bullet(date, mtg_id)?;
+
+        // Verify deletion
+        let after = j.bullets(date)?;
+        assert_eq!(after.len(), 1, "Should have one bullet after deletion");
+        assert_eq!(after[0].text, "Regular Task", "Regular task should remain");
+        assert!(!after.iter().any(|b| b.text == "Team Sync"), "Meeting should be deleted");
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]  // Prevent parallel test runs
+    fn test_migrate_to_specific_date() -> Result<()> {
+        let env = TestEnv::new();
+        let j = &env.journal;
+        let from = NaiveDate::from_ymd_opt(2025, 11, 4).unwrap();
+        let to = NaiveDate::from_ymd_opt(2025, 11, 10).unwrap();
+
+        // Create a bullet on source date
+        j.add_bullet(from, "Task for next week", Some(2), &["work".to_string()], &[])?;
+
+        // Get the bullet ID
+        let initial = j.bullets(from)?;
+        assert_eq!(initial.len(), 1, "Should have one bullet");
+        let bullet_id = initial[0].visible_index;
+
+        // Migrate to specific date
+        j.migrate_one(from, to, bullet_id)?;
+
+        // Verify source only has the forwarding marker
+        let source_after = j.bullets(from)?;
+        assert_eq!(source_after.len(), 1, "Source should keep a forwarding marker");
+        assert_eq!(source_after[0].state, State::Migrated, "Source bullet should be marked [>]");
+        assert_eq!(source_after[0].migrated_to, Some(to), "Forwarding marker should point at the target");
+
+        // Verify target has the bullet
+        let target = j.bullets(to)?;
+        assert_eq!(target.len(), 1, "Target should have one bullet");
+        assert_eq!(target[0].text, "Task for next week", "Bullet text should match");
+        assert_eq!(target[0].migrated_from, Some(from), "Target should record its source");
+        assert_eq!(target[0].priority, Some(2), "Priority should be preserved");
+        assert_eq!(target[0].tags, vec!["work"], "Tags should be preserved");
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]  // Prevent parallel test runs
+    fn test_migrate_open_to_specific_date() -> Result<()> {
+        let env = TestEnv::new();
+        let j = &env.journal;
+        let from = NaiveDate::from_ymd_opt(2025, 11, 3).unwrap();
+        let to = NaiveDate::from_ymd_opt(2025, 11, 15).unwrap();
+
+        // Add bullets with different states
+        j.add_bullet(from, "Done Task", None, &[], &[])?;
+        j.add_bullet(from, "Open Task 1", Some(1), &["tag1".to_string()], &[])?;
+        j.add_bullet(from, "Open Task 2", Some(2), &["tag2".to_string()], &[])?;
+
+        // Mark first task done
+        let initial = j.bullets(from)?;
+        let done_id = initial.iter()
+            .find(|b| b.text == "Done Task")
+            .map(|b| b.visible_index)
+            .expect("Should find done task");
+        j.mark_done(from, done_id)?;
+
+        // Migrate all open tasks to specific date
+        let moved = j.migrate_open(from, to)?;
+        assert_eq!(moved.len(), 2, "Both open tasks should be reported as moved");
+
+        // Verify source has no open tasks left
+        let source_after = j.bullets(from)?;
+        assert_eq!(source_after.iter().filter(|b| b.is_open()).count(), 0, "Source should have no open bullets");
+        assert!(source_after[0].is_done(), "Completed bullet should stay completed");
+
+        // Verify target has both open tasks, in their original order
+        let target = j.bullets(to)?;
+        assert_eq!(target.len(), 2, "Target should have two bullets");
+        assert_eq!(target[0].text, "Open Task 1", "Open Task 1 should be migrated first");
+        assert_eq!(target[1].text, "Open Task 2", "Open Task 2 should be migrated second");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ids_survive_delete_and_migration() -> Result<()> {
+        let env = TestEnv::new();
+        let j = &env.journal;
+        let from = NaiveDate::from_ymd_opt(2025, 11, 4).unwrap();
+        let to = NaiveDate::from_ymd_opt(2025, 11, 10).unwrap();
+
+        let a = j.add_bullet(from, "Task A", None, &[], &[])?;
+        let b = j.add_bullet(from, "Task B", None, &[], &[])?;
+        let c = j.add_bullet(from, "Task C", None, &[], &[])?;
+        let (a_id, b_id, c_id) = (a.id.unwrap(), b.id.unwrap(), c.id.unwrap());
+        assert!(a_id != b_id && b_id != c_id && a_id != c_id, "IDs should be unique");
+
+        // Deleting the first bullet shifts positions but not IDs
+        j.delete_bullet(from, BulletRef::Id(a_id))?;
+        let done = j.mark_done(from, BulletRef::Id(c_id.clone()))?;
+        assert_eq!(done.text, "Task C", "ID should still address Task C after a delete");
+
+        // Migration keeps the ID
+        j.migrate_one(from, to, BulletRef::Id(b_id.clone()))?;
+        let target = j.bullets(to)?;
+        assert_eq!(target[0].id.as_deref(), Some(b_id.as_str()), "Migrated bullet should keep its ID");
+        assert!(j.mark_done(from, BulletRef::Id(b_id)).is_err(), "Migrated ID should no longer be on the source day");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_signifiers_cancel_and_migration() -> Result<()> {
+        let env = TestEnv::new();
+        let j = &env.journal;
+        let from = NaiveDate::from_ymd_opt(2025, 11, 4).unwrap();
+        let to = NaiveDate::from_ymd_opt(2025, 11, 5).unwrap();
+
+        j.add_bullet(from, "Task to cancel", None, &[], &[])?;
+        j.add_entry(from, Kind::Event, "Launch", None, &[], &[])?;
+        j.add_entry(from, Kind::Note, "Felt good", None, &[], &[])?;
+        j.add_bullet(from, "Open task", None, &[], &[])?;
+
+        let cancelled = j.cancel_bullet(from, 1)?;
+        assert_eq!(cancelled.state, State::Cancelled, "Cancel should return the new state");
+        assert!(read_file_lines(&j.file_for(from))?[0].starts_with("- [-] "), "Cancelled marker should be written");
+        assert!(j.mark_done(from, 1).is_err(), "Cancelled task cannot be completed");
+        assert!(j.mark_done(from, 2).is_err(), "Events cannot be completed");
+
+        // Only open tasks move; events, notes and cancelled tasks stay put
+        let moved = j.migrate_open(from, to)?;
+        assert_eq!(moved.len(), 1, "Only the open task should migrate");
+        let kinds: Vec<Kind> = j.bullets(to)?.iter().map(|b| b.kind).collect();
+        assert_eq!(kinds, vec![Kind::Task], "Events and notes should stay on the source day");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_migration_moves_notes() -> Result<()> {
+        let env = TestEnv::new();
+        let j = &env.journal;
+        let from = NaiveDate::from_ymd_opt(2025, 11, 4).unwrap();
+        let to = NaiveDate::from_ymd_opt(2025, 11, 5).unwrap();
+        let later = NaiveDate::from_ymd_opt(2025, 11, 6).unwrap();
+
+        let notes = vec!["call first".to_string(), "ask for Sam".to_string()];
+        let a = j.add_bullet(from, "Book dentist", None, &[], &notes)?;
+        j.add_bullet(from, "Plan trip", None, &[], &["check flights".to_string()])?;
+        let mut lines = read_file_lines(&j.file_for(from))?;
+        lines.extend(["  - [ ] Pick hotel".to_string(), "    more detail".to_string(), "- Loose note".to_string()]);
+        write_file_lines(&j.file_for(from), &lines)?;
+
+        // Single migration carries the whole note block
+        j.migrate_one(from, to, BulletRef::Id(a.id.unwrap()))?;
+        let target = j.bullets(to)?;
+        assert_eq!(target[0].notes, notes, "Notes should move with the bullet");
+        let source = j.bullets(from)?;
+        assert!(source[0].notes.is_empty(), "Forwarding marker should not keep the notes");
+        assert_eq!(source[1].text, "Plan trip", "Next bullet should not inherit orphaned notes");
+
+        // Bulk migration carries notes and indented sub-tasks exactly once
+        let moved = j.migrate_open(from, later)?;
+        assert_eq!(moved.len(), 1, "Sub-task should travel inside its parent");
+        let target = read_file_lines(&j.file_for(later))?;
+        assert_eq!(target.len(), 4, "Bullet, note, sub-task and detail should move");
+        assert_eq!(&target[1..], ["  - note: check flights", "  - [ ] Pick hotel", "    more detail"]);
+        let source = j.bullets(from)?;
+        assert_eq!(source.last().unwrap().text, "Loose note", "Content after the block should stay");
+        assert_eq!(source.len(), 3, "Source should keep two markers and the loose note");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_apply_migration_decisions() -> Result<()> {
+        let env = TestEnv::new();
+        let j = &env.journal;
+        let from = NaiveDate::from_ymd_opt(2025, 11, 4).unwrap();
+        let to = NaiveDate::from_ymd_opt(2025, 11, 5).unwrap();
+        let later = NaiveDate::from_ymd_opt(2025, 12, 1).unwrap();
+        for t in ["Forward me", "Schedule me", "Finished", "Drop me", "Keep me"] {
+            j.add_bullet(from, t, None, &[], &[])?;
+        }
+
+        // A bad decision anywhere means nothing is written
+        let bad = vec![(BulletRef::from(1), Decision::Forward(to)), (BulletRef::from(9), Decision::Done)];
+        assert!(j.apply_migration(from, bad).is_err(), "Unknown bullet should be rejected");
+        assert_eq!(j.bullets(from)?.iter().filter(|b| b.is_open()).count(), 5, "Failed review should not touch the day");
+        assert!(j.apply_migration(from, vec![(1.into(), Decision::Forward(from))]).is_err(), "Moving to the same day should fail");
+
+        let applied = j.apply_migration(from, vec![
+            (5.into(), Decision::Keep),
+            (2.into(), Decision::Schedule(later)),
+            (1.into(), Decision::Forward(to)),
+            (3.into(), Decision::Done),
+            (4.into(), Decision::Cancel),
+        ])?;
+        assert_eq!(applied[0].0.text, "Forward me", "Results should be in file order");
+
+        let states: Vec<State> = j.bullets(from)?.iter().map(|b| b.state).collect();
+        assert_eq!(states, vec![State::Migrated, State::Scheduled, State::Done, State::Cancelled, State::Open]);
+        assert_eq!(j.bullets(to)?[0].text, "Forward me", "Forwarded bullet should arrive");
+        assert_eq!(j.bullets(later)?[0].migrated_from, Some(from), "Scheduled bullet should record its origin");
+        assert_eq!(j.migration_candidates(from)?.len(), 1, "Only the kept bullet remains to review");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_update_bullet_in_place() -> Result<()> {
+        let env = TestEnv::new();
+        let j = &env.journal;
+        let date = NaiveDate::from_ymd_opt(2025, 11, 4).unwrap();
+        j.add_bullet(date, "First", None, &[], &[])?;
+        let b = j.add_bullet(date, "Secnd", None, &["work".to_string()], &["old note".to_string()])?;
+        j.add_bullet(date, "Third", None, &[], &[])?;
+        j.mark_done(date, 2)?;
+
+        let updated = j.update_bullet(date, BulletRef::Id(b.id.clone().unwrap()), |b| {
+            b.text = "Second".to_string();
+            b.priority = Some(3);
+            b.notes = vec!["new note".to_string(), "another".to_string()];
+        })?;
+        assert_eq!(updated.visible_index, 2, "Bullet should stay in place");
+
+        let bullets = j.bullets(date)?;
+        let texts: Vec<&str> = bullets.iter().map(|b| b.text.as_str()).collect();
+        assert_eq!(texts, vec!["First", "Second", "Third"], "Order should be unchanged");
+        assert!(bullets[1].is_done(), "State should survive an edit");
+        assert_eq!(bullets[1].priority, Some(3), "Priority should be updated");
+        assert_eq!(bullets[1].tags, vec!["work"], "Tags should be kept");
+        assert_eq!(bullets[1].notes, vec!["new note", "another"], "Notes should be replaced");
+        assert_eq!(bullets[1].id, b.id, "ID should be kept");
+        assert!(j.update_bullet(date, 1, |b| b.text.clear()).is_err(), "Empty text should be rejected");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_meetings_across_midnight() -> Result<()> {
+        let env = TestEnv::new();
+        let j = &env.journal;
+        let (d1, d2) = (NaiveDate::from_ymd_opt(2025, 11, 6).unwrap(), NaiveDate::from_ymd_opt(2025, 11, 7).unwrap());
+        let at = |d: NaiveDate, h, m| d.and_hms_opt(h, m, 0).unwrap();
+        j.add_meeting(d1, NaiveTime::from_hms_opt(23, 30, 0).unwrap(), 90, None, "Late deploy", &[], &[])?;
+        j.add_meeting(d2, NaiveTime::from_hms_opt(0, 10, 0).unwrap(), 30, None, "Early call", &[], &[])?;
+
+        let after_midnight = j.meetings_between(at(d2, 0, 0), at(d2, 1, 0))?;
+        let texts: Vec<&str> = after_midnight.iter().map(|m| m.bullet.text.as_str()).collect();
+        assert_eq!(texts, vec!["Late deploy", "Early call"], "A meeting running past midnight belongs to both days");
+        assert_eq!((after_midnight[0].date, after_midnight[0].end), (d1, at(d2, 1, 0)), "End should be on the next day");
+        assert!(j.meetings_between(at(d2, 1, 0), at(d2, 2, 0))?.is_empty(), "Intervals are half-open");
+
+        let due = j.clone().with_clock(Clock::fixed(at(d1, 23, 55))).take_upcoming_meetings(15)?;
+        assert_eq!(due.len(), 1, "Only meetings starting in the window are announced");
+        assert_eq!((due[0].bullet.text.as_str(), due[0].date, due[0].minutes_until), ("Early call", d2, 15), "Tomorrow's meeting should be announced before midnight");
+        assert!(j.clone().with_clock(Clock::fixed(at(d2, 0, 0))).take_upcoming_meetings(15)?.is_empty(), "Meetings are announced once");
+        Ok(())
+    }
+
+    #[test]
+    fn test_meeting_conflicts_and_free_slots() -> Result<()> {
+        let env = TestEnv::new();
+        let j = &env.journal;
+        let date = NaiveDate::from_ymd_opt(2025, 11, 6).unwrap();
+        let t = |h, m| NaiveTime::from_hms_opt(h, m, 0).unwrap();
+        j.add_meeting(date, t(10, 0), 60, None, "Design review", &[], &[])?;
+        j.add_meeting(date, t(10, 30), 60, None, "Overlapping sync", &[], &[])?;
+        j.add_meeting(date, t(14, 0), 30, None, "1:1", &[], &[])?;
+        let cancelled = j.add_meeting(date, t(16, 0), 60, None, "Dropped", &[], &[])?;
+        j.cancel_bullet(date, BulletRef::Id(cancelled.id.unwrap()))?;
+
+        let texts = |ms: Vec<Meeting>| ms.into_iter().map(|m| m.bullet.text).collect::<Vec<_>>();
+        assert_eq!(texts(j.meeting_conflicts(date, t(11, 0), 30, None)?), vec!["Overlapping sync"]);
+        assert_eq!(texts(j.meeting_conflicts(date, t(9, 30), 60, None)?), vec!["Design review"]);
+        assert!(j.meeting_conflicts(date, t(11, 30), 30, None)?.is_empty(), "Back-to-back meetings don't conflict");
+        assert!(j.meeting_conflicts(date, t(16, 0), 30, None)?.is_empty(), "Cancelled meetings don't conflict");
+
+        let slots: Vec<(NaiveTime, NaiveTime)> = j.free_slots(date, t(9, 0), t(18, 0), 30)?.into_iter().map(|(a, b)| (a.time(), b.time())).collect();
+        assert_eq!(slots, vec![(t(9, 0), t(10, 0)), (t(11, 30), t(14, 0)), (t(14, 30), t(18, 0))]);
+        assert_eq!(j.free_slots(date, t(9, 0), t(18, 0), 180)?.len(), 1, "Short gaps should be dropped");
+        assert!(j.free_slots(date, t(18, 0), t(9, 0), 30).is_err(), "Window must run forwards");
+        Ok(())
+    }
+
+    #[test]
+    fn test_replace_bullet_from_block() -> Result<()> {
+        let env = TestEnv::new();
+        let j = &env.journal;
+        let date = NaiveDate::from_ymd_opt(2025, 11, 6).unwrap();
+        j.add_bullet(date, "First", None, &[], &[])?;
+        let mtg = j.add_meeting(date, NaiveTime::from_hms_opt(10, 0, 0).unwrap(), 30, None, "Sync", &["work".to_string()], &["agenda".to_string()])?;
+        j.add_bullet(date, "Last", None, &[], &[])?;
+
+        let block = mtg.block();
+        assert_eq!(block, vec![format!("- [ ] [mtg 10:00 30] Sync #work ^{}", mtg.id.clone().unwrap()), "  - note: agenda".to_string()]);
+        let edited = vec!["- [x] [mtg 11:15 45 Europe/Berlin] (!) Sync with Berlin #work #remote".to_string(), "  - note: new agenda".to_string()];
+        let b = j.replace_bullet(date, BulletRef::Id(mtg.id.clone().unwrap()), &edited)?;
+        assert_eq!(b.id, mtg.id, "ID should be kept even when removed from the block");
+
+        let bullets = j.bullets(date)?;
+        let texts: Vec<&str> = bullets.iter().map(|b| b.text.as_str()).collect();
+        assert_eq!(texts, vec!["First", "Sync with Berlin", "Last"], "Bullet should stay in place");
+        let b = &bullets[1];
+        assert!(b.is_done(), "Marker should be taken from the block");
+        assert_eq!((b.meeting_time, b.meeting_duration_min, b.meeting_tz), (NaiveTime::from_hms_opt(11, 15, 0), Some(45), Some(chrono_tz::Europe::Berlin)));
+        assert_eq!((b.priority, b.tags.clone(), b.notes.clone()), (Some(1), vec!["work".to_string(), "remote".to_string()], vec!["new agenda".to_string()]));
+        assert!(j.replace_bullet(date, 2, &["not a bullet".to_string()]).is_err(), "A block without a bullet should be rejected");
+        assert!(j.replace_bullet(date, 2, &["- [ ] One".to_string(), "- [ ] Two".to_string()]).is_err(), "Only one bullet may be edited");
+        Ok(())
+    }
+
+    #[test]
+    fn test_concurrent_writers_lose_nothing() -> Result<()> {
+        let env = TestEnv::new();
+        let date = NaiveDate::from_ymd_opt(2025, 11, 6).unwrap();
+        let root = env.journal.root().to_path_buf();
+        let writers: Vec<_> = (0..2).map(|w| {
+            let root = root.clone();
+            std::thread::spawn(move || -> Result<()> {
+                let j = Journal::open(root)?;
+                for i in 0..20 { j.add_bullet(date, &format!("Writer {} task {}", w, i), None, &[], &[])?; }
+                Ok(())
+            })
+        }).collect();
+        for w in writers { w.join().expect("writer thread")?; }
+
+        let bullets = env.journal.bullets(date)?;
+        assert_eq!(bullets.len(), 40, "Every add should survive concurrent writers");
+        let mut ids: Vec<_> = bullets.iter().filter_map(|b| b.id.clone()).collect();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), 40, "IDs should stay unique");
+        let leftovers = fs::read_dir(&root)?.filter_map(|e| e.ok()).filter(|e| e.file_name().to_string_lossy().ends_with(".tmp")).count();
+        assert_eq!(leftovers, 0, "No temp files should be left behind");
+        Ok(())
+    }
+
+    #[test]
+    fn test_journal_is_rooted_at_given_dir() -> Result<()> {
+        let env = TestEnv::new();
+        let date = NaiveDate::from_ymd_opt(2025, 11, 6).unwrap();
+        env.journal.add_bullet(date, "Rooted", None, &[], &[])?;
+        let path = env.journal.file_for(date);
+        assert_eq!(path, env.journal.root().join("2025-11-06.md"), "Day file should live under the journal root");
+        assert!(path.exists(), "Day file should be written");
+        Ok(())
+    }
+}
@@ -0,0 +1,49 @@
+//! Library behind the `bj` bullet journal CLI.
+//!
+//! A [`Journal`] is a directory of Markdown files, one per day
+//! (`YYYY-MM-DD.md`), plus one per month for the monthly and future logs
+//! (`YYYY-MM.md`) and named collections under `collections/`. Each bullet
+//! starts with a signifier (`- [ ]` task, `- [x]` done, `- [>]` migrated,
+//! `- [<]` scheduled, `- [-]` cancelled, `- [o]` event, or a plain `- `
+//! note) followed by an optional priority marker, a `[mtg HH:MM D]`
+//! meeting prefix, `#tags`, a trailing persistent `^id`, and indented
+//! `  - note:` lines underneath:
+//!
+//! ```no_run
+//! use bullet_journal::Journal;
+//! use chrono::NaiveDate;
+//!
+//! let journal = Journal::open("/tmp/my-journal")?;
+//! let date = NaiveDate::from_ymd_opt(2025, 11, 6).unwrap();
+//! journal.add_bullet(date, "Draft project plan", Some(2), &["work".into()], &[])?;
+//! for b in journal.bullets(date)? {
+//!     println!("{} {}", b.visible_index, b.text);
+//! }
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+mod bullet;
+mod clock;
+mod collection;
+mod config;
+mod date;
+mod export;
+mod ics;
+mod journal;
+mod month;
+mod recur;
+mod search;
+mod undo;
+#[cfg(test)]
+mod test_support;
+
+pub use bullet::{parse_bullets, parse_priority_opt, parse_text_meeting_meta, parse_text_meta_only, Bullet, BulletRef, Kind, State};
+pub use clock::Clock;
+pub use config::{Config, Defaults, Display, Glyphs, Source, COLOR_ROLES, LOCAL_DIR};
+pub use date::{month_of, parse_date, parse_date_at, parse_month_at, DateRange};
+pub use export::{write_bullets, Format, FIELDS};
+pub use ics::ImportReport;
+pub use journal::{default_dir, write_atomic, Decision, Journal, Meeting, UpcomingMeeting};
+pub use recur::{Freq, Recurring, Rule};
+pub use search::{Page, Query, SearchHit};
+pub use undo::Operation;
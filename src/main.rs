@@ -1,24 +1,44 @@
-use std::fs::{self, OpenOptions};
-use std::io::{Read, Write};
+use anyhow::{bail, Context, Result};
+use chrono::{Datelike, NaiveDate, NaiveTime, Weekday};
+use chrono_tz::Tz;
+use colored::{ColoredString, Colorize};
+use clap::{Args, Parser, Subcommand};
+
+use std::fs;
+use std::io::{self, IsTerminal, Write};
 use std::path::{Path, PathBuf};
 
-use anyhow::{bail, Context, Result};
-use chrono::{Datelike, Local, NaiveDate, NaiveTime};
-use colored::Colorize;
-use clap::{Parser, Subcommand};
-use directories::ProjectDirs;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::terminal;
+use unicode_width::UnicodeWidthStr;
+
+use theme::theme;
+
+mod theme;
+mod tui;
+
+use bullet_journal::{parse_date_at, parse_month_at, parse_priority_opt, DateRange, write_atomic, write_bullets, Bullet, BulletRef, Clock, Config, Decision, Format, Journal, Kind, Page, Query, Rule, SearchHit, Source, State};
 
 #[derive(Parser)]
 #[command(
     name = "bj",
     version,
     about = "Bullet journal CLI",
-    long_about = "A fast terminal bullet journal that stores Markdown per day.\n\nFeatures:\n- Add/list/done/delete/migrate bullets with priority, tags, notes\n- Week and month calendar views\n- Meetings: add/list/notify with start time and duration\n- Optional daily and meeting notifications (systemd user timers)",
-    after_help = "Examples:\n  bj add \"Draft project plan\"\n  bj add -p high -t work -n \"prep\" \"Release train\"\n  bj list -t work -p 3\n  bj done 2\n  bj delete 3\n  bj migrate --from 2025-11-04\n  bj migrate --from 2025-11-04 --to 2025-11-10\n  bj migrate --from 2025-11-04 --to 2025-11-10 --id 2\n  bj week -t work\n  bj cal\n  bj meeting add -t 15:00 -u 30 \"Team sync\"\n  bj meeting list\n  bj meeting notify -w 15"
+    long_about = "A fast terminal bullet journal that stores Markdown per day.\n\nFeatures:\n- Add/list/done/cancel/delete/migrate bullets with priority, tags, notes\n- Tasks, events and notes with bullet journal signifiers\n- Week and month calendar views\n- Full-screen TUI for moving between days and weeks\n- Search across the whole journal\n- Meetings: add/list/notify with start time and duration\n- Recurring bullets and meetings (daily, weekdays, weekly, monthly, RRULE)\n- Optional daily and meeting notifications (systemd user timers)",
+    after_help = "Examples:\n  bj add \"Draft project plan\"\n  bj add -p high -t work -n \"prep\" \"Release train\"\n  bj add -d \"next fri\" \"Weekly report\"\n  bj list -t work -p 3\n  bj list --range 2025-11-01..2025-11-07\n  bj done 2\n  bj done ^k3x9m2qa\n  bj done 1 3 ^k3x9m2qa\n  bj done --where tag:errands --since -1w\n  bj cancel 4\n  bj note \"Felt productive today\"\n  bj event \"Product launch\"\n  bj delete 3\n  bj edit ^k3x9m2qa -p high -t urgent --time 15:30\n  bj edit 2\n  bj migrate --from 2025-11-04\n  bj migrate --from 2025-11-04 --to 2025-11-10\n  bj migrate --from 2025-11-04 --to 2025-11-10 --id 2\n  bj migrate --since -1w --where tag:work\n  bj migrate -i\n  bj history ^k3x9m2qa\n  bj future add --month 2026-03 \"Renew passport\"\n  bj month list\n  bj migrate --month\n  bj collection add \"Reading list\" \"Dune\"\n  bj collection migrate Ideas 2 --from yesterday\n  bj undo\n  bj redo\n  bj week -t work\n  bj cal\n  bj tui\n  bj search tag:work status:open after:2025-11-01\n  bj week --format json | jq '.[] | select(.state == \"open\")'\n  bj meeting add -t 15:00 -u 30 \"Team sync\"\n  bj meeting add -t 15:00 --tz Europe/Berlin \"Berlin sync\"\n  bj export ics --tasks -o ~/calendar/bj.ics\n  bj import ics ~/Downloads/work.ics\n  bj meeting add -t 09:30 -u 15 --every weekdays \"Standup\"\n  bj meeting list\n  bj meeting free --min 45 --between 09:00-17:00\n  bj meeting notify -w 15\n  bj --journal work list\n  bj journals"
 )] 
 struct Cli {
 	#[command(subcommand)]
 	action: Action,
+	/// Output for read commands: json, csv or tsv instead of the terminal view
+	#[arg(long = "format", global = true)]
+	format: Option<Format>,
+	/// Journal to use: a name from the config file, or a path such as ./notes or ~/notes
+	#[arg(short = 'J', long = "journal", global = true)]
+	journal: Option<String>,
+	/// Pretend it is this local time (YYYY-MM-DDTHH:MM or a date), for scripts and tests
+	#[arg(long = "now", global = true, hide = true)]
+	now: Option<String>,
 }
 
 #[derive(Subcommand)]
@@ -27,8 +47,8 @@ enum Action {
 	Add {
 		/// Bullet text
 		text: Vec<String>,
-		/// Date YYYY-MM-DD (default: today)
-		#[arg(short = 'd', long = "date")]
+		/// Date (YYYY-MM-DD, fri, +3d, ...; default: today)
+		#[arg(short = 'd', long = "date", allow_hyphen_values = true)]
 		date: Option<String>,
 		/// Priority: low, med, high (or 1/2/3)
 		#[arg(short = 'p', long = "priority")]
@@ -39,12 +59,20 @@ enum Action {
 		/// Optional note lines (can repeat)
 		#[arg(short = 'n', long = "note")]
 		notes: Vec<String>,
+		/// Repeat: daily, weekdays, weekly:mon,thu, monthly:15 or an RRULE (starts at --date)
+		#[arg(short = 'e', long = "every")]
+		every: Option<String>,
+		/// Last date of the recurrence (YYYY-MM-DD, eom, +2w, ...)
+		#[arg(long = "until", allow_hyphen_values = true, requires = "every")]
+		until: Option<String>,
 	},
 	/// List bullets for a date (default today)
 	List {
-		/// Date YYYY-MM-DD (default: today)
-		#[arg(short = 'd', long = "date")]
+		/// Date (YYYY-MM-DD, fri, +3d, ...; default: today)
+		#[arg(short = 'd', long = "date", allow_hyphen_values = true, conflicts_with_all = ["range", "since", "until"])]
 		date: Option<String>,
+		#[command(flatten)]
+		days: RangeArgs,
 		/// Filter by tag (can repeat)
 		#[arg(short = 't', long = "tag")]
 		tags: Vec<String>,
@@ -52,38 +80,105 @@ enum Action {
 		#[arg(short = 'p', long = "priority")]
 		priority: Option<String>,
 	},
-	/// Mark a bullet done by ID for a date (default today)
+	/// Mark bullets done by ID, or every bullet matching --where, for a date (default today) or range
 	Done {
-		/// Bullet ID (1-based visible index)
-		id: usize,
-		/// Date YYYY-MM-DD (default: today)
-		#[arg(short = 'd', long = "date")]
+		/// Bullet IDs: 1-based visible index or persistent ID (e.g. ^a1b2)
+		#[arg(required_unless_present = "filter")]
+		ids: Vec<BulletRef>,
+		/// Date (YYYY-MM-DD, fri, +3d, ...; default: today)
+		#[arg(short = 'd', long = "date", allow_hyphen_values = true, conflicts_with_all = ["range", "since", "until"])]
+		date: Option<String>,
+		#[command(flatten)]
+		days: RangeArgs,
+		/// Pick bullets with a search query instead, e.g. "tag:work status:open"
+		#[arg(long = "where")]
+		filter: Option<String>,
+	},
+	/// Cancel a task (mark it no longer relevant) by ID for a date (default today)
+	Cancel {
+		/// Bullet ID: 1-based visible index or persistent ID (e.g. ^a1b2)
+		id: BulletRef,
+		/// Date (YYYY-MM-DD, fri, +3d, ...; default: today)
+		#[arg(short = 'd', long = "date", allow_hyphen_values = true)]
+		date: Option<String>,
+	},
+	/// Add a note bullet to a date (default today)
+	Note {
+		/// Note text
+		text: Vec<String>,
+		/// Date (YYYY-MM-DD, fri, +3d, ...; default: today)
+		#[arg(short = 'd', long = "date", allow_hyphen_values = true)]
 		date: Option<String>,
+		/// One or more tags
+		#[arg(short = 't', long = "tag")]
+		tags: Vec<String>,
+	},
+	/// Add an event bullet to a date (default today)
+	Event {
+		/// Event text
+		text: Vec<String>,
+		/// Date (YYYY-MM-DD, fri, +3d, ...; default: today)
+		#[arg(short = 'd', long = "date", allow_hyphen_values = true)]
+		date: Option<String>,
+		/// One or more tags
+		#[arg(short = 't', long = "tag")]
+		tags: Vec<String>,
+		/// Optional note lines (can repeat)
+		#[arg(short = 'n', long = "note")]
+		notes: Vec<String>,
 	},
 	/// Delete a bullet or meeting by ID for a date (default today)
 	Delete {
-		/// Bullet or meeting ID (1-based visible index)
-		id: usize,
-		/// Date YYYY-MM-DD (default: today)
-		#[arg(short = 'd', long = "date")]
+		/// Bullet or meeting IDs: 1-based visible index or persistent ID (e.g. ^a1b2)
+		#[arg(required_unless_present = "filter")]
+		ids: Vec<BulletRef>,
+		/// Date (YYYY-MM-DD, fri, +3d, ...; default: today)
+		#[arg(short = 'd', long = "date", allow_hyphen_values = true, conflicts_with_all = ["range", "since", "until"])]
+		date: Option<String>,
+		#[command(flatten)]
+		days: RangeArgs,
+		/// Pick bullets with a search query instead, e.g. "status:cancelled"
+		#[arg(long = "where")]
+		filter: Option<String>,
+	},
+	/// Change a bullet or meeting in place; without flags, opens $EDITOR on it
+	Edit {
+		/// Bullet or meeting ID: 1-based visible index or persistent ID (e.g. ^a1b2)
+		id: BulletRef,
+		/// Date (YYYY-MM-DD, fri, +3d, ...; default: today)
+		#[arg(short = 'd', long = "date", allow_hyphen_values = true)]
 		date: Option<String>,
+		#[command(flatten)]
+		changes: EditArgs,
 	},
-	/// Migrate all open bullets from a date to another date (default: from yesterday to today)
+	/// Migrate open bullets from a date, range or month collection to another date (default: from yesterday to today)
 	Migrate {
-		/// Source date YYYY-MM-DD (default: yesterday)
-		#[arg(long = "from")]
+		/// Source date (YYYY-MM-DD, fri, +3d, ...; default: yesterday)
+		#[arg(long = "from", allow_hyphen_values = true, conflicts_with_all = ["range", "since", "until"])]
 		from: Option<String>,
-		/// Target date YYYY-MM-DD (default: today)
-		#[arg(long = "to")]
+		#[command(flatten)]
+		days: RangeArgs,
+		/// Target date (YYYY-MM-DD, fri, +3d, ...; default: today)
+		#[arg(long = "to", allow_hyphen_values = true)]
 		to: Option<String>,
-		/// Optional bullet ID to migrate (1-based). If omitted, all open bullets are migrated.
-		#[arg(long = "id")]
-		id: Option<usize>,
+		/// Bullet to migrate (1-based index or ^id; can repeat). If omitted, all open bullets are migrated.
+		#[arg(long = "id", value_name = "ID", conflicts_with = "interactive")]
+		ids: Vec<BulletRef>,
+		/// Migrate the bullets matching a search query, e.g. "tag:work"
+		#[arg(long = "where", conflicts_with = "interactive")]
+		filter: Option<String>,
+		/// Pull the open items of a month collection (default: this month) into the daily log
+		#[arg(long = "month", num_args = 0..=1, default_missing_value = "today", allow_hyphen_values = true,
+			conflicts_with_all = ["from", "range", "since", "until", "filter", "interactive"])]
+		month: Option<String>,
+		/// Review each open bullet: forward, schedule, done, cancel or keep
+		#[arg(short = 'i', long = "interactive")]
+		interactive: bool,
 	},
 	/// Show a weekly view for the week containing date (default: today)
 	Week {
-		/// Any date in the target week YYYY-MM-DD (default: today)
-		#[arg(short = 'd', long = "date")]
+		/// Any date in the target week (YYYY-MM-DD, fri, +3d, ...; default: today)
+		#[arg(short = 'd', long = "date", allow_hyphen_values = true)]
 		date: Option<String>,
 		/// Filter by tag (can repeat)
 		#[arg(short = 't', long = "tag")]
@@ -100,9 +195,356 @@ enum Action {
 	/// Show a month calendar with markers for bullets/meetings
 	Cal {
 		/// Any date in the month (default today)
-		#[arg(short = 'd', long = "date")]
+		#[arg(short = 'd', long = "date", allow_hyphen_values = true)]
 		date: Option<String>,
 	},
+	/// Future log: items for coming months that don't have a day yet
+	Future {
+		#[command(subcommand)]
+		cmd: FutureCmd,
+	},
+	/// Monthly log: the collection kept for a month beside its days
+	Month {
+		#[command(subcommand)]
+		cmd: MonthCmd,
+	},
+	/// Named pages not tied to a date, such as a reading list or ideas
+	Collection {
+		#[command(subcommand)]
+		cmd: CollectionCmd,
+	},
+	/// Skip one occurrence of a recurring bullet or meeting
+	Skip {
+		/// Bullet ID: 1-based visible index or persistent ID (e.g. ^a1b2)
+		id: BulletRef,
+		/// Date (YYYY-MM-DD, fri, +3d, ...; default: today)
+		#[arg(short = 'd', long = "date", allow_hyphen_values = true)]
+		date: Option<String>,
+	},
+	/// Export the journal to other formats
+	Export {
+		#[command(subcommand)]
+		cmd: ExportCmd,
+	},
+	/// Import meetings from other formats
+	Import {
+		#[command(subcommand)]
+		cmd: ImportCmd,
+	},
+	/// Manage recurring bullets and meetings: list/remove
+	Recur {
+		#[command(subcommand)]
+		cmd: RecurCmd,
+	},
+	/// Open the full-screen journal for a date (default: today)
+	Tui {
+		/// Date (YYYY-MM-DD, fri, +3d, ...; default: today)
+		#[arg(short = 'd', long = "date", allow_hyphen_values = true)]
+		date: Option<String>,
+	},
+	/// Revert the last change to the journal
+	Undo,
+	/// Re-apply the last undone change
+	Redo,
+	/// Show the configured journals and which one is in use
+	Journals,
+	/// Show where a bullet has been: every day it was migrated through
+	History {
+		/// Persistent bullet ID (e.g. ^a1b2)
+		id: String,
+	},
+	/// Search every day in the journal
+	Search {
+		/// Words (substring), /regex/, tag:NAME, priority:P, status:open|done, is:meeting, before:DATE, after:DATE; covers day files, month pages and collections
+		#[arg(required = true)]
+		query: Vec<String>,
+	},
+}
+
+#[derive(Subcommand)]
+enum FutureCmd {
+	/// Add an item to the collection of a month
+	Add {
+		/// Month (YYYY-MM, +2m, or any date in it)
+		#[arg(short = 'm', long = "month", allow_hyphen_values = true)]
+		month: String,
+		#[command(flatten)]
+		entry: EntryArgs,
+	},
+	/// List the collections of this month and every month after it
+	List,
+}
+
+#[derive(Subcommand)]
+enum MonthCmd {
+	/// Add an item to the collection of a month (default this month)
+	Add {
+		/// Month (YYYY-MM, +2m, or any date in it; default: this month)
+		#[arg(short = 'm', long = "month", allow_hyphen_values = true)]
+		month: Option<String>,
+		#[command(flatten)]
+		entry: EntryArgs,
+	},
+	/// List the collection of a month (default this month)
+	List {
+		/// Month (YYYY-MM, +2m, or any date in it; default: this month)
+		#[arg(short = 'm', long = "month", allow_hyphen_values = true)]
+		month: Option<String>,
+	},
+}
+
+/// An item for a month or named collection.
+#[derive(Args)]
+struct EntryArgs {
+	/// Item text
+	#[arg(required = true)]
+	text: Vec<String>,
+	/// Log it as an event instead of a task
+	#[arg(long = "event")]
+	event: bool,
+	/// Priority: low, med, high (or 1/2/3)
+	#[arg(short = 'p', long = "priority")]
+	priority: Option<String>,
+	/// One or more tags
+	#[arg(short = 't', long = "tag")]
+	tags: Vec<String>,
+	/// Optional note lines (can repeat)
+	#[arg(short = 'n', long = "note")]
+	notes: Vec<String>,
+}
+
+impl EntryArgs {
+	/// `-p`, or the configured default for tasks.
+	fn priority(&self, config: &Config) -> Result<Option<u8>> {
+		match &self.priority {
+			Some(p) => parse_priority_opt(Some(p)),
+			None if !self.event => config.default_priority(),
+			None => Ok(None),
+		}
+	}
+
+	fn kind(&self) -> Kind {
+		if self.event { Kind::Event } else { Kind::Task }
+	}
+
+	fn add_to_month(&self, journal: &Journal, config: &Config, month: NaiveDate) -> Result<()> {
+		let b = journal.add_to_month(month, self.kind(), &self.text.join(" "), self.priority(config)?, &config.with_default_tags(&self.tags), &self.notes)?;
+		println!("Added ^{} to {}", b.id.unwrap_or_default(), journal.file_for_month(month).display());
+		Ok(())
+	}
+}
+
+#[derive(Subcommand)]
+enum CollectionCmd {
+	/// Start an empty collection
+	New {
+		name: String,
+	},
+	/// List every collection with its open task count
+	List,
+	/// Add an item to a collection
+	Add {
+		name: String,
+		#[command(flatten)]
+		entry: EntryArgs,
+	},
+	/// Mark items of a collection done
+	Done {
+		name: String,
+		/// Bullet IDs: 1-based visible index or persistent ID (e.g. ^a1b2)
+		#[arg(required = true)]
+		ids: Vec<BulletRef>,
+	},
+	/// Show the items of a collection
+	Show {
+		name: String,
+	},
+	/// Move open items from the collection to a day (--to), or from a day into the collection (--from)
+	Migrate {
+		name: String,
+		/// Bullet IDs: 1-based visible index or persistent ID (e.g. ^a1b2)
+		#[arg(required = true)]
+		ids: Vec<BulletRef>,
+		/// Day to move the items to (YYYY-MM-DD, fri, +3d, ...)
+		#[arg(long = "to", allow_hyphen_values = true, required_unless_present = "from", conflicts_with = "from")]
+		to: Option<String>,
+		/// Day to take the items from (YYYY-MM-DD, fri, +3d, ...)
+		#[arg(long = "from", allow_hyphen_values = true)]
+		from: Option<String>,
+	},
+}
+
+#[derive(Subcommand)]
+enum RecurCmd {
+	/// List recurring definitions
+	List,
+	/// Remove a recurring definition (occurrences already in day files are kept)
+	Remove {
+		/// Recurring ID (e.g. ^a1b2)
+		id: String,
+	},
+}
+
+#[derive(Subcommand)]
+enum ExportCmd {
+	/// Meetings (and optionally open tasks) as an iCalendar file
+	Ics {
+		/// First date (YYYY-MM-DD, fri, +3d, ...; default: first day in the journal)
+		#[arg(long = "from", allow_hyphen_values = true)]
+		from: Option<String>,
+		/// Last date (YYYY-MM-DD, fri, +3d, ...; default: 90 days from today, or the last day in the journal if later)
+		#[arg(long = "to", allow_hyphen_values = true)]
+		to: Option<String>,
+		/// Also export open tasks as to-dos due on their day
+		#[arg(long = "tasks")]
+		tasks: bool,
+		/// Write to this file (replaced atomically, so calendars can subscribe to it) instead of stdout
+		#[arg(short = 'o', long = "output")]
+		output: Option<PathBuf>,
+	},
+}
+
+#[derive(Subcommand)]
+enum ImportCmd {
+	/// VEVENTs from an iCalendar file; re-importing updates instead of duplicating
+	Ics {
+		/// Path to the .ics file
+		file: PathBuf,
+		/// Expand recurring events from this date (YYYY-MM-DD, fri, +3d, ...; default: today)
+		#[arg(long = "from", allow_hyphen_values = true)]
+		from: Option<String>,
+		/// Expand recurring events up to this date (YYYY-MM-DD, fri, +3d, ...; default: 90 days from --from)
+		#[arg(long = "until", allow_hyphen_values = true)]
+		until: Option<String>,
+	},
+}
+
+impl Action {
+	fn is_interactive(&self) -> bool {
+		matches!(self, Action::Edit { changes, .. } if changes.is_empty()) || matches!(self, Action::Migrate { interactive: true, .. })
+	}
+
+	/// Undo label for commands that change the journal; `None` for ones that
+	/// only read it, which run without taking the lock.
+	fn change_label(&self) -> Option<&'static str> {
+		Some(match self {
+			Action::Add { .. } => "add",
+			Action::Done { .. } => "done",
+			Action::Cancel { .. } => "cancel",
+			Action::Note { .. } => "note",
+			Action::Event { .. } => "event",
+			Action::Delete { .. } => "delete",
+			Action::Edit { .. } => "edit",
+			Action::Migrate { .. } => "migrate",
+			Action::Skip { .. } => "skip",
+			Action::Meeting { cmd: MeetingCmd::Add { .. } } => "meeting add",
+			Action::Meeting { cmd: MeetingCmd::Notify { .. } } => "meeting notify",
+			Action::Future { cmd: FutureCmd::Add { .. } } => "future add",
+			Action::Month { cmd: MonthCmd::Add { .. } } => "month add",
+			Action::Collection { cmd: CollectionCmd::New { .. } } => "collection new",
+			Action::Collection { cmd: CollectionCmd::Add { .. } } => "collection add",
+			Action::Collection { cmd: CollectionCmd::Done { .. } } => "collection done",
+			Action::Collection { cmd: CollectionCmd::Migrate { .. } } => "collection migrate",
+			Action::Import { cmd: ImportCmd::Ics { .. } } => "import ics",
+			Action::Recur { cmd: RecurCmd::Remove { .. } } => "recur remove",
+			Action::List { .. } | Action::Week { .. } | Action::Cal { .. } | Action::Search { .. } | Action::History { .. }
+			| Action::Export { .. } | Action::Journals | Action::Tui { .. } | Action::Undo | Action::Redo
+			| Action::Meeting { cmd: MeetingCmd::List { .. } | MeetingCmd::Free { .. } }
+			| Action::Future { cmd: FutureCmd::List } | Action::Month { cmd: MonthCmd::List { .. } }
+			| Action::Collection { cmd: CollectionCmd::List | CollectionCmd::Show { .. } }
+			| Action::Recur { cmd: RecurCmd::List } => return None,
+		})
+	}
+}
+
+/// A span of days, as an alternative to a single date.
+#[derive(Args)]
+struct RangeArgs {
+	/// Inclusive range FROM..TO, e.g. 2025-11-01..2025-11-07 or -1w.. (an open end is today)
+	#[arg(long = "range", allow_hyphen_values = true, conflicts_with_all = ["since", "until"])]
+	range: Option<String>,
+	/// Start of the range (the end defaults to today)
+	#[arg(long = "since", allow_hyphen_values = true)]
+	since: Option<String>,
+	/// End of the range (the start defaults to today)
+	#[arg(long = "until", allow_hyphen_values = true)]
+	until: Option<String>,
+}
+
+impl RangeArgs {
+	fn is_set(&self) -> bool {
+		self.range.is_some() || self.since.is_some() || self.until.is_some()
+	}
+
+	/// The range given, or just `date` (today when not given).
+	fn resolve(&self, date: Option<&str>, today: NaiveDate) -> Result<DateRange> {
+		if let Some(r) = &self.range { return DateRange::parse_at(r, today); }
+		if !self.is_set() { return Ok(DateRange::day(parse_or_today(date, today)?)); }
+		DateRange::new(parse_or_today(self.since.as_deref(), today)?, parse_or_today(self.until.as_deref(), today)?)
+	}
+}
+
+/// Field changes for `bj edit`; anything not given is left as it is.
+#[derive(Args)]
+struct EditArgs {
+	/// New text
+	#[arg(long = "text")]
+	text: Option<String>,
+	/// Priority: low, med, high (or 1/2/3), or none
+	#[arg(short = 'p', long = "priority")]
+	priority: Option<String>,
+	/// Add a tag (can repeat)
+	#[arg(short = 't', long = "tag")]
+	tags: Vec<String>,
+	/// Remove a tag (can repeat)
+	#[arg(short = 'r', long = "untag")]
+	untags: Vec<String>,
+	/// Add a note line (can repeat)
+	#[arg(short = 'n', long = "note")]
+	notes: Vec<String>,
+	/// Remove existing notes (before adding --note lines)
+	#[arg(long = "clear-notes")]
+	clear_notes: bool,
+	/// Meeting start time HH:MM (24h)
+	#[arg(long = "time")]
+	time: Option<String>,
+	/// Meeting duration in minutes
+	#[arg(short = 'u', long = "duration")]
+	duration: Option<u32>,
+	/// IANA time zone of the meeting time, or "local"
+	#[arg(short = 'z', long = "tz")]
+	tz: Option<String>,
+}
+
+impl EditArgs {
+	fn is_empty(&self) -> bool {
+		self.text.is_none() && self.priority.is_none() && self.tags.is_empty() && self.untags.is_empty() && self.notes.is_empty()
+			&& !self.clear_notes && self.time.is_none() && self.duration.is_none() && self.tz.is_none()
+	}
+
+	/// `b` with these changes applied.
+	fn apply(&self, mut b: Bullet) -> Result<Bullet> {
+		if let Some(t) = &self.text { b.text = t.trim().to_string(); }
+		if let Some(p) = &self.priority {
+			b.priority = if p.eq_ignore_ascii_case("none") { None } else { parse_priority_opt(Some(p))? };
+		}
+		for t in &self.tags {
+			let t = t.trim_start_matches('#');
+			if !b.tags.iter().any(|bt| bt == t) { b.tags.push(t.to_string()); }
+		}
+		b.tags.retain(|bt| !self.untags.iter().any(|u| u.trim_start_matches('#') == bt));
+		if self.clear_notes { b.notes.clear(); }
+		b.notes.extend(self.notes.iter().cloned());
+		if let Some(t) = &self.time { b.meeting_time = Some(parse_time(t)?); }
+		if b.meeting_time.is_none() && (self.duration.is_some() || self.tz.is_some()) {
+			bail!("bullet is not a meeting; give --time to make it one");
+		}
+		if let Some(d) = self.duration { b.meeting_duration_min = Some(d); }
+		if let Some(z) = &self.tz {
+			b.meeting_tz = if z.eq_ignore_ascii_case("local") { None } else { Some(z.parse().map_err(|_| anyhow::anyhow!("unknown time zone: {}", z))?) };
+		}
+		Ok(b)
+	}
 }
 
 #[derive(Subcommand)]
@@ -111,377 +553,798 @@ enum MeetingCmd {
 	Add {
 		/// Title
 		title: Vec<String>,
-		/// Date YYYY-MM-DD (default: today)
-		#[arg(short = 'd', long = "date")]
+		/// Date (YYYY-MM-DD, fri, +3d, ...; default: today)
+		#[arg(short = 'd', long = "date", allow_hyphen_values = true)]
 		date: Option<String>,
 		/// Start time HH:MM (24h)
 		#[arg(short = 't', long = "time")]
 		time: String,
-		/// Duration minutes
-		#[arg(short = 'u', long = "duration", default_value_t = 60)]
-		duration: u32,
+		/// Duration minutes (default: 60, or defaults.meeting_minutes)
+		#[arg(short = 'u', long = "duration")]
+		duration: Option<u32>,
 		/// Tags
 		#[arg(short = 'g', long = "tag")]
 		tags: Vec<String>,
 		/// Notes
 		#[arg(short = 'n', long = "note")]
 		notes: Vec<String>,
+		/// Repeat: daily, weekdays, weekly:mon,thu, monthly:15 or an RRULE (starts at --date)
+		#[arg(short = 'e', long = "every")]
+		every: Option<String>,
+		/// Last date of the recurrence (YYYY-MM-DD, eom, +2w, ...)
+		#[arg(long = "until", allow_hyphen_values = true, requires = "every")]
+		until: Option<String>,
+		/// IANA time zone the time is given in, e.g. Europe/Berlin (default: local time)
+		#[arg(short = 'z', long = "tz")]
+		tz: Option<Tz>,
+		/// Refuse to add the meeting if it overlaps another one
+		#[arg(long = "strict")]
+		strict: bool,
+	},
+	/// Show open slots between meetings
+	Free {
+		/// Date (YYYY-MM-DD, fri, +3d, ...; default: today)
+		#[arg(short = 'd', long = "date", allow_hyphen_values = true)]
+		date: Option<String>,
+		/// Shortest slot to show, in minutes
+		#[arg(short = 'm', long = "min", default_value_t = 30)]
+		min: u32,
+		/// Working hours to search, HH:MM-HH:MM
+		#[arg(short = 'b', long = "between", default_value = "09:00-18:00")]
+		between: String,
 	},
 	/// List meetings for a date (default today)
 	List {
-		#[arg(short = 'd', long = "date")]
+		#[arg(short = 'd', long = "date", allow_hyphen_values = true, conflicts_with_all = ["range", "since", "until"])]
 		date: Option<String>,
+		#[command(flatten)]
+		days: RangeArgs,
 	},
 	/// Send notifications for meetings starting within N minutes (default 15)
 	Notify {
-		#[arg(short = 'w', long = "window", default_value_t = 15)]
-		window_minutes: i64,
+		/// Minutes ahead to look (default: 15, or defaults.notify_window)
+		#[arg(short = 'w', long = "window")]
+		window_minutes: Option<i64>,
 	},
 }
 
+
 fn main() -> Result<()> {
 	let cli = Cli::parse();
+	let config = Config::load()?;
+	theme::init(&config)?;
+	let (dir, source) = config.select_current(cli.journal.as_deref())?;
+	if let Action::Journals = cli.action { return list_journals(&config, &dir, source); }
+	let clock = cli.now.as_deref().map(Clock::parse).transpose()?.unwrap_or_default();
+	let journal = Journal::open(&dir)?.with_clock(clock);
 	match cli.action {
-		Action::Add { text, date, priority, tags, notes } => {
-			let date = parse_or_today(date.as_deref())?;
-			let pr = parse_priority_opt(priority.as_deref())?;
-			add_bullet(date, &text.join(" "), pr, &tags, &notes)?
+		Action::Tui { date } => tui::run(&journal, &config, parse_or_today(date.as_deref(), clock.today())?),
+		Action::Undo => match journal.undo()? {
+			Some(op) => { println!("Undid: {} ({})", op.label, op.files.join(", ")); Ok(()) }
+			None => { println!("{}", "Nothing to undo".dimmed()); Ok(()) }
+		},
+		Action::Redo => match journal.redo()? {
+			Some(op) => { println!("Redid: {} ({})", op.label, op.files.join(", ")); Ok(()) }
+			None => { println!("{}", "Nothing to redo".dimmed()); Ok(()) }
+		},
+		// Commands that wait for the user lock the journal only while they write.
+		action if action.is_interactive() => run(&journal, &config, action, cli.format),
+		// Every other change is one undoable operation; reads take no lock.
+		action => match action.change_label() {
+			Some(label) => journal.operation(label, || run(&journal, &config, action, cli.format)),
+			None => run(&journal, &config, action, cli.format),
+		},
+	}
+}
+
+fn run(journal: &Journal, config: &Config, action: Action, format: Option<Format>) -> Result<()> {
+	let today = journal.clock().today();
+	match action {
+		Action::Add { text, date, priority, tags, notes, every, until } => {
+			let date = parse_or_today(date.as_deref(), today)?;
+			let pr = match priority { Some(p) => parse_priority_opt(Some(&p))?, None => config.default_priority()? };
+			let tags = config.with_default_tags(&tags);
+			if let Some(rule) = parse_recurrence(every.as_deref(), until.as_deref(), today)? {
+				let r = journal.add_recurring(date, rule, &text.join(" "), pr, &tags, &notes)?;
+				println!("Added recurring ^{} ({}) from {}", r.id(), r.rule, date);
+			} else {
+				let b = journal.add_bullet(date, &text.join(" "), pr, &tags, &notes)?;
+				println!("Added ^{} to {}", b.id.unwrap_or_default(), journal.file_for(date).display());
+			}
 		}
-		Action::List { date, tags, priority } => {
-			let date = parse_or_today(date.as_deref())?;
+		Action::List { date, days, tags, priority } => {
+			let range = days.resolve(date.as_deref(), today)?;
 			let pr = parse_priority_opt(priority.as_deref())?;
-			list_bullets(date, &tags, pr)?
+			match format {
+				Some(f) => emit(f, dated(journal, range.days(), &tags, pr)?)?,
+				None if range.is_single_day() => list_bullets(journal, range.from, &tags, pr)?,
+				None => range_view(journal, range, &tags, pr)?,
+			}
+		}
+		Action::Done { ids, date, days, filter } => {
+			let range = days.resolve(date.as_deref(), today)?;
+			let hits = pick(journal, range, &ids, filter.as_deref())?;
+			for h in &hits {
+				journal.mark_done(h.date, hit_ref(&h.bullet))?;
+				println!("Marked done: {} #{} - \"{}\"", h.date, hit_ref(&h.bullet), h.bullet.text);
+			}
 		}
-		Action::Done { id, date } => {
-			let date = parse_or_today(date.as_deref())?;
-			mark_done(date, id)?
+		Action::Cancel { id, date } => {
+			let date = parse_or_today(date.as_deref(), today)?;
+			let b = journal.cancel_bullet(date, id.clone())?;
+			println!("Cancelled: {} #{} - \"{}\"", date, id, b.text);
 		}
-		Action::Delete { id, date } => {
-			let date = parse_or_today(date.as_deref())?;
-			delete_bullet(date, id)?
+		Action::Note { text, date, tags } => {
+			let date = parse_or_today(date.as_deref(), today)?;
+			let tags = config.with_default_tags(&tags);
+			let b = journal.add_entry(date, Kind::Note, &text.join(" "), None, &tags, &[])?;
+			println!("Added note ^{} to {}", b.id.unwrap_or_default(), journal.file_for(date).display());
 		}
-		Action::Migrate { from, to, id } => {
-			let from_date = match from {
-				Some(d) => parse_date(&d)?,
-				None => {
-					let today = Local::now().date_naive();
-					today.pred_opt().context("cannot compute yesterday")?
-				}
+		Action::Event { text, date, tags, notes } => {
+			let date = parse_or_today(date.as_deref(), today)?;
+			let tags = config.with_default_tags(&tags);
+			let b = journal.add_entry(date, Kind::Event, &text.join(" "), None, &tags, &notes)?;
+			println!("Added event ^{} to {}", b.id.unwrap_or_default(), journal.file_for(date).display());
+		}
+		Action::Delete { ids, date, days, filter } => {
+			let range = days.resolve(date.as_deref(), today)?;
+			// Last first, so positions of the bullets still to go don't shift.
+			for h in pick(journal, range, &ids, filter.as_deref())?.iter().rev() {
+				let b = journal.delete_bullet(h.date, hit_ref(&h.bullet))?;
+				println!("Deleted: {} #{} - \"{}\"", h.date, hit_ref(&h.bullet), b.text);
+			}
+		}
+		Action::Edit { id, date, changes } => {
+			let date = parse_or_today(date.as_deref(), today)?;
+			let Some(b) = journal.bullets(date)?.into_iter().find(|b| id.matches(b)) else { bail!("bullet {} not found", id) };
+			let b = if changes.is_empty() {
+				let block = b.block();
+				let edited = edit_in_editor(&block)?;
+				if edited == block { println!("{}", "No changes".dimmed()); return Ok(()); }
+				journal.operation("edit", || {
+					let current = journal.bullets(date)?.into_iter().find(|b| id.matches(b));
+					if current.map(|b| b.block()) != Some(block) { bail!("bullet {} changed while it was being edited; try again", id); }
+					journal.replace_bullet(date, id.clone(), &edited)
+				})?
+			} else {
+				let updated = changes.apply(b)?;
+				journal.update_bullet(date, id.clone(), |b| *b = updated)?
+			};
+			println!("Edited: {} #{} - \"{}\"", date, id, b.text);
+		}
+		Action::Migrate { to, ids, month: Some(month), .. } => {
+			let month = parse_month_at(&month, today)?;
+			let to_date = parse_or_today(to.as_deref(), today)?;
+			let moved = journal.migrate_month(month, to_date, &ids)?;
+			if moved.is_empty() { println!("No open items to migrate from {}", month.format("%Y-%m")); }
+			for b in moved {
+				println!("{}", format!("Migrated {} from {} to {}", b.text, month.format("%Y-%m"), to_date).color(theme().colors.done));
+			}
+		}
+		Action::Migrate { from, days, to, ids, filter, interactive, month: None } => {
+			let range = match from {
+				Some(d) => DateRange::day(parse_date_at(&d, today)?),
+				None if days.is_set() => days.resolve(None, today)?,
+				None => DateRange::day(today.pred_opt().context("cannot compute yesterday")?),
 			};
-			let to_date = parse_or_today(to.as_deref())?;
-			if let Some(bid) = id {
-				migrate_one(from_date, to_date, bid)?;
+			let to_date = parse_or_today(to.as_deref(), today)?;
+			// The target day may sit inside the range; its bullets stay put.
+			let sources = range.days().filter(|d| *d != to_date);
+			if interactive {
+				for from_date in sources { review_migration(journal, from_date, to_date)?; }
+			} else if !ids.is_empty() || filter.is_some() {
+				for h in pick(journal, range, &ids, filter.as_deref())? {
+					if h.date == to_date { continue; }
+					journal.migrate_one(h.date, to_date, hit_ref(&h.bullet))?;
+					println!("{}", format!("Migrated bullet {} from {} to {}", hit_ref(&h.bullet), h.date, to_date).color(theme().colors.done));
+				}
 			} else {
-				migrate_open(from_date, to_date)?;
+				let mut moved = 0;
+				for from_date in sources {
+					let n = journal.migrate_open(from_date, to_date)?.len();
+					if n > 0 { println!("Migrated {} open bullet(s) from {} to {}", n, from_date, to_date); }
+					moved += n;
+				}
+				if moved == 0 { println!("No open bullets to migrate from {}", range); }
 			}
 		}
 		Action::Week { date, tags, priority } => {
-			let base = parse_or_today(date.as_deref())?;
+			let base = parse_or_today(date.as_deref(), today)?;
 			let pr = parse_priority_opt(priority.as_deref())?;
-			week_view(base, &tags, pr)?
+			match format {
+				Some(f) => {
+					let start = theme().week_of(base);
+					emit(f, dated(journal, (0..7).map(|i| start + chrono::Days::new(i)), &tags, pr)?)?
+				}
+				None => week_view(journal, base, &tags, pr)?,
+			}
 		}
 		Action::Meeting { cmd } => match cmd {
-			MeetingCmd::Add { title, date, time, duration, tags, notes } => {
-				let date = parse_or_today(date.as_deref())?;
-				let time = NaiveTime::parse_from_str(&time, "%H:%M").with_context(|| format!("invalid time: {}", time))?;
-				add_meeting(date, time, duration, &title.join(" "), &tags, &notes)?
+			MeetingCmd::Add { title, date, time, duration, tags, notes, every, until, tz, strict } => {
+				let date = parse_or_today(date.as_deref(), today)?;
+				let time = parse_time(&time)?;
+				let duration = duration.or(config.defaults.meeting_minutes).unwrap_or(60);
+				let tags = config.with_default_tags(&tags);
+				let conflicts = journal.meeting_conflicts(date, time, duration, tz)?;
+				if !conflicts.is_empty() {
+					let clash: Vec<String> = conflicts.iter().map(|m| format!("{} {} ({}m)", m.start.format("%H:%M"), m.bullet.text, (m.end - m.start).num_minutes())).collect();
+					if strict { bail!("overlaps {}", clash.join(", ")); }
+					println!("{}", format!("Warning: overlaps {}", clash.join(", ")).color(theme().colors.warning));
+				}
+				if let Some(rule) = parse_recurrence(every.as_deref(), until.as_deref(), today)? {
+					let r = journal.add_recurring_meeting(date, rule, time, duration, tz, &title.join(" "), &tags, &notes)?;
+					println!("Added recurring ^{} ({}) from {}", r.id(), r.rule, date);
+				} else {
+					let b = journal.add_meeting(date, time, duration, tz, &title.join(" "), &tags, &notes)?;
+					println!("Added ^{} to {}", b.id.unwrap_or_default(), journal.file_for(date).display());
+				}
+			}
+			MeetingCmd::Free { date, min, between } => {
+				let date = parse_or_today(date.as_deref(), today)?;
+				let (from, to) = between.split_once('-').with_context(|| format!("invalid range: {} (expected HH:MM-HH:MM)", between))?;
+				free_slots(journal, date, parse_time(from)?, parse_time(to)?, min)?
 			}
-			MeetingCmd::List { date } => {
-				let date = parse_or_today(date.as_deref())?;
-				list_meetings(date)?
+			MeetingCmd::List { date, days } => {
+				let range = days.resolve(date.as_deref(), today)?;
+				match format {
+					Some(f) => {
+						let mut hits = Vec::new();
+						for date in range.days() { hits.extend(journal.meetings(date)?.into_iter().map(|bullet| SearchHit { date, page: Page::Day, bullet })); }
+						emit(f, hits)?
+					}
+					None => list_meetings(journal, range)?,
+				}
 			}
 			MeetingCmd::Notify { window_minutes } => {
-				notify_upcoming_meetings(window_minutes)?
+				notify_upcoming_meetings(journal, window_minutes.or(config.defaults.notify_window).unwrap_or(15))?
+			}
+		},
+		Action::Future { cmd } => match cmd {
+			FutureCmd::Add { month, entry } => entry.add_to_month(journal, config, parse_month_at(&month, today)?)?,
+			FutureCmd::List => {
+				let log = journal.future_log(today)?;
+				match format {
+					Some(f) => emit(f, log.into_iter().flat_map(|(date, bullets)| bullets.into_iter().map(move |bullet| SearchHit { date, page: Page::Month, bullet })).collect())?,
+					None if log.is_empty() => println!("{}", "The future log is empty".dimmed()),
+					None => for (month, bullets) in log { month_log(month, &bullets); },
+				}
+			}
+		},
+		Action::Month { cmd } => match cmd {
+			MonthCmd::Add { month, entry } => entry.add_to_month(journal, config, parse_month_at(month.as_deref().unwrap_or("today"), today)?)?,
+			MonthCmd::List { month } => {
+				let month = parse_month_at(month.as_deref().unwrap_or("today"), today)?;
+				let bullets = journal.month_bullets(month)?;
+				match format {
+					Some(f) => emit(f, bullets.into_iter().map(|bullet| SearchHit { date: month, page: Page::Month, bullet }).collect())?,
+					None if bullets.is_empty() => println!("{}", format!("Nothing in the collection for {}", month.format("%B %Y")).dimmed()),
+					None => month_log(month, &bullets),
+				}
+			}
+		},
+		Action::Collection { cmd } => match cmd {
+			CollectionCmd::New { name } => {
+				let path = journal.new_collection(&name)?;
+				println!("Created {}", path.display());
+			}
+			CollectionCmd::List => {
+				let names = journal.collections()?;
+				if names.is_empty() { println!("{}", "No collections yet".dimmed()); }
+				for name in names {
+					let bullets = journal.collection_bullets(&name)?;
+					let open = bullets.iter().filter(|b| b.is_open()).count();
+					println!("{}  {}", name.bold(), format!("{} open / {}", open, bullets.len()).dimmed());
+				}
+			}
+			CollectionCmd::Add { name, entry } => {
+				let b = journal.add_to_collection(&name, entry.kind(), &entry.text.join(" "), entry.priority(config)?, &config.with_default_tags(&entry.tags), &entry.notes)?;
+				println!("Added ^{} to {}", b.id.unwrap_or_default(), name);
+			}
+			CollectionCmd::Done { name, ids } => {
+				for id in ids {
+					let b = journal.collection_done(&name, id.clone())?;
+					println!("Marked done: {} #{} - \"{}\"", name, id, b.text);
+				}
+			}
+			CollectionCmd::Show { name } => {
+				let bullets = journal.collection_bullets(&name)?;
+				println!("\n{}", name.bold().color(theme().colors.accent));
+				if bullets.is_empty() { println!("   {}", "Nothing here yet".dimmed().italic()); }
+				page_rows(&bullets);
+			}
+			CollectionCmd::Migrate { name, ids, to, from } => {
+				let c = theme().colors.done;
+				for id in ids {
+					match (&to, &from) {
+						(Some(to), _) => {
+							let to = parse_date_at(to, today)?;
+							let b = journal.migrate_from_collection(&name, id, to)?;
+							println!("{}", format!("Migrated {} from {} to {}", b.text, name, to).color(c));
+						}
+						(None, from) => {
+							let from = parse_or_today(from.as_deref(), today)?;
+							let b = journal.migrate_to_collection(from, id, &name)?;
+							println!("{}", format!("Migrated {} from {} to {}", b.text, from, name).color(c));
+						}
+					}
+				}
 			}
 		},
 		Action::Cal { date } => {
-			let base = parse_or_today(date.as_deref())?;
-			month_calendar(base)?
+			let base = parse_or_today(date.as_deref(), today)?;
+			match format {
+				Some(f) => {
+					let first = base.with_day(1).context("invalid month")?;
+					let days = first.iter_days().take_while(|d| d.month() == first.month());
+					emit(f, dated(journal, days, &[], None)?)?
+				}
+				None => month_calendar(journal, base)?,
+			}
+		}
+		Action::Export { cmd: ExportCmd::Ics { from, to, tasks, output } } => {
+			let days = journal.days()?;
+			let from = match from {
+				Some(d) => parse_date_at(&d, today)?,
+				None => days.first().copied().unwrap_or(today),
+			};
+			let to = match to {
+				Some(d) => parse_date_at(&d, today)?,
+				None => days.last().copied().unwrap_or(today).max(today + chrono::Days::new(90)),
+			};
+			let ics = journal.export_ics(from, to, tasks)?;
+			match output {
+				Some(path) => {
+					write_atomic(&path, ics.as_bytes())?;
+					println!("Exported {} to {} to {}", from, to, path.display());
+				}
+				None => print!("{}", ics),
+			}
+		}
+		Action::Import { cmd: ImportCmd::Ics { file, from, until } } => {
+			let ics = fs::read_to_string(&file).with_context(|| format!("cannot read {}", file.display()))?;
+			let from = parse_or_today(from.as_deref(), today)?;
+			let until = match until {
+				Some(d) => parse_date_at(&d, today)?,
+				None => from + chrono::Days::new(90),
+			};
+			let report = journal.import_ics(&ics, from, until)?;
+			for w in &report.warnings { println!("{}", format!("warning: {}", w).color(theme().colors.warning)); }
+			println!("{}", format!(
+				"Imported {}: {} added, {} updated, {} cancelled, {} unchanged",
+				file.display(), report.added, report.updated, report.cancelled, report.unchanged,
+			).color(theme().colors.done));
+		}
+		Action::Skip { id, date } => {
+			let date = parse_or_today(date.as_deref(), today)?;
+			let b = journal.skip_occurrence(date, id.clone())?;
+			println!("Skipped: {} #{} - \"{}\"", date, id, b.text);
+		}
+		Action::Recur { cmd } => match cmd {
+			RecurCmd::List => match format {
+				Some(f) => {
+					let series = journal.recurring()?.into_iter().map(|r| SearchHit { date: r.start, page: Page::Day, bullet: Bullet { recurring: true, ..r.template } });
+					emit(f, series.collect())?
+				}
+				None => list_recurring(journal)?,
+			},
+			RecurCmd::Remove { id } => {
+				let r = journal.remove_recurring(id.trim_start_matches('^'))?;
+				println!("Removed recurring ^{} - \"{}\"", r.id(), r.template.text);
+			}
+		},
+		Action::Tui { .. } | Action::Undo | Action::Redo | Action::Journals => unreachable!("handled in main"),
+		Action::History { id } => match format {
+			Some(f) => emit(f, journal.history(id.trim_start_matches('^'))?)?,
+			None => history(journal, id.trim_start_matches('^'))?,
+		},
+		Action::Search { query } => {
+			let query = Query::parse_at(&query.join(" "), today)?;
+			match format {
+				Some(f) => emit(f, journal.search(&query)?)?,
+				None => search(journal, &query)?,
+			}
 		}
 	}
 	Ok(())
 }
 
-fn parse_or_today(s: Option<&str>) -> Result<NaiveDate> {
+fn parse_or_today(s: Option<&str>, today: NaiveDate) -> Result<NaiveDate> {
 	match s {
-		Some(v) => parse_date(v),
-		None => Ok(Local::now().date_naive()),
+		Some(v) => parse_date_at(v, today),
+		None => Ok(today),
 	}
 }
 
-fn parse_date(s: &str) -> Result<NaiveDate> {
-	NaiveDate::parse_from_str(s, "%Y-%m-%d").with_context(|| format!("invalid date: {}", s))
+/// Open `$VISUAL`/`$EDITOR` (default `vi`) on `block` and return the saved lines.
+fn edit_in_editor(block: &[String]) -> Result<Vec<String>> {
+	let editor = std::env::var("VISUAL").or_else(|_| std::env::var("EDITOR")).unwrap_or_else(|_| "vi".to_string());
+	let path = std::env::temp_dir().join(format!("bj-edit-{}.md", std::process::id()));
+	fs::write(&path, block.iter().map(|l| format!("{}\n", l)).collect::<String>())?;
+	let mut words = editor.split_whitespace();
+	let program = words.next().context("$EDITOR is empty")?;
+	let status = std::process::Command::new(program).args(words).arg(&path).status();
+	let edited = fs::read_to_string(&path);
+	let _ = fs::remove_file(&path);
+	let status = status.with_context(|| format!("cannot run editor: {}", editor))?;
+	if !status.success() { bail!("editor exited with {}; bullet left unchanged", status); }
+	Ok(edited?.lines().filter(|l| !l.trim().is_empty()).map(str::to_string).collect())
 }
 
-fn data_dir() -> Result<PathBuf> {
-	let proj = ProjectDirs::from("dev", "local", "bullet_journal").context("cannot resolve project dirs")?;
-	let dir = proj.data_dir().to_path_buf();
-	fs::create_dir_all(&dir).with_context(|| format!("create data dir {}", dir.display()))?;
-	Ok(dir)
+fn parse_time(s: &str) -> Result<NaiveTime> {
+	NaiveTime::parse_from_str(s.trim(), "%H:%M").with_context(|| format!("invalid time: {}", s))
 }
 
-fn file_for(date: NaiveDate) -> Result<PathBuf> {
-	let dir = data_dir()?;
-	let fname = format!("{}-{:02}-{:02}.md", date.year(), date.month(), date.day());
-	Ok(dir.join(fname))
+fn parse_recurrence(every: Option<&str>, until: Option<&str>, today: NaiveDate) -> Result<Option<Rule>> {
+	let Some(every) = every else { return Ok(None) };
+	let mut rule = Rule::parse(every)?;
+	if let Some(u) = until { rule.until = Some(parse_date_at(u, today)?); }
+	Ok(Some(rule))
 }
 
-#[derive(Debug, Clone)]
-struct Bullet {
-	line_index: usize, // index in file content lines
-	visible_index: usize, // 1-based index among bullet lines
-	completed: bool,
-	text: String,
-	priority: Option<u8>,
-	tags: Vec<String>,
-	notes: Vec<String>,
-	meeting_time: Option<NaiveTime>,
-	meeting_duration_min: Option<u32>,
+/// Bullets for each of `days` that pass the tag and priority filters.
+fn dated(journal: &Journal, days: impl IntoIterator<Item = NaiveDate>, tags: &[String], priority: Option<u8>) -> Result<Vec<SearchHit>> {
+	let mut hits = Vec::new();
+	for date in days {
+		hits.extend(journal.bullets(date)?.into_iter().filter(|b| b.matches(tags, priority)).map(|bullet| SearchHit { date, page: Page::Day, bullet }));
+	}
+	Ok(hits)
 }
 
-fn read_file_lines(path: &Path) -> Result<Vec<String>> {
-	if !path.exists() {
-		return Ok(vec![]);
+/// Bullets in `range` named by `ids` or matching the `--where` query.
+fn pick(journal: &Journal, range: DateRange, ids: &[BulletRef], filter: Option<&str>) -> Result<Vec<SearchHit>> {
+	let query = filter.map(|f| Query::parse_at(f, journal.clock().today())).transpose()?;
+	let hits = journal.select(range, ids, query.as_ref())?;
+	if hits.is_empty() { bail!("no bullets match in {}", range); }
+	Ok(hits)
+}
+
+/// The steadiest way to point at `b` again: its `^id`, else its position.
+fn hit_ref(b: &Bullet) -> BulletRef {
+	match &b.id {
+		Some(id) => BulletRef::Id(id.clone()),
+		None => BulletRef::Index(b.visible_index),
 	}
-	let mut f = OpenOptions::new().read(true).open(path).with_context(|| format!("open {}", path.display()))?;
-	let mut s = String::new();
-	f.read_to_string(&mut s).with_context(|| format!("read {}", path.display()))?;
-	Ok(s.lines().map(|l| l.to_string()).collect())
 }
 
-fn write_file_lines(path: &Path, lines: &[String]) -> Result<()> {
-	let mut f = OpenOptions::new().create(true).truncate(true).write(true).open(path).with_context(|| format!("write {}", path.display()))?;
-	let contents = if lines.is_empty() { String::new() } else { format!("{}\n", lines.join("\n")) };
-	f.write_all(contents.as_bytes()).with_context(|| format!("write {}", path.display()))?;
-	Ok(())
+fn emit(format: Format, hits: Vec<SearchHit>) -> Result<()> {
+	let mut out = io::stdout().lock();
+	write_bullets(&mut out, format, &hits)
+}
+
+/// A month collection under its month heading.
+fn month_log(month: NaiveDate, bullets: &[Bullet]) {
+	println!("\n{}", month.format("%B %Y").to_string().bold().color(theme().colors.accent));
+	page_rows(bullets);
 }
 
-fn parse_bullets(lines: &[String]) -> Vec<Bullet> {
-	let mut out = Vec::new();
-	let mut visible = 0usize;
-	let mut idx = 0usize;
-	while idx < lines.len() {
-		let line = &lines[idx];
-		let trimmed = line.trim_start();
-		if let Some(rest) = trimmed.strip_prefix("- [ ] ") {
-			visible += 1;
-			let (text, pr, tags, mt, dur) = parse_text_meeting_meta(rest);
-			let notes = collect_notes(lines, idx + 1);
-			out.push(Bullet { line_index: idx, visible_index: visible, completed: false, text, priority: pr, tags, notes, meeting_time: mt, meeting_duration_min: dur });
-		} else if let Some(rest) = trimmed.strip_prefix("- [x] ") {
-			visible += 1;
-			let (text, pr, tags, mt, dur) = parse_text_meeting_meta(rest);
-			let notes = collect_notes(lines, idx + 1);
-			out.push(Bullet { line_index: idx, visible_index: visible, completed: true, text, priority: pr, tags, notes, meeting_time: mt, meeting_duration_min: dur });
+/// Bullets of a page that isn't a day, one row each with their notes.
+fn page_rows(bullets: &[Bullet]) {
+	let c = &theme().colors;
+	for b in bullets {
+		let tags_str = if b.tags.is_empty() { String::new() } else { format!("  {}", b.tags.join(" ")) };
+		println!("{:>3} {} {} {}{}{}", b.visible_index.to_string().color(c.muted), glyph(b), priority_icon(b), bullet_text(b, false), tags_str.color(c.tag).italic(), trailer(b).color(c.muted));
+		for (i, n) in b.notes.iter().enumerate() {
+			println!("        {} {}", branch(i, b.notes.len()), n.dimmed());
 		}
-		idx += 1;
 	}
-	out
 }
 
-fn collect_notes(lines: &[String], mut from: usize) -> Vec<String> {
-	let mut notes = Vec::new();
-	while from < lines.len() {
-		let l = &lines[from];
-		if let Some(n) = l.strip_prefix("  - note: ") {
-			notes.push(n.to_string());
-			from += 1;
-			continue;
-		}
-		break;
+fn list_recurring(journal: &Journal) -> Result<()> {
+	let all = journal.recurring()?;
+	if all.is_empty() { println!("{}", "No recurring bullets".dimmed()); return Ok(()); }
+	let c = &theme().colors;
+	for r in all {
+		let t = &r.template;
+		let time_str = t.meeting_time.map(|m| format!("{} ", m.format("%H:%M"))).unwrap_or_default();
+		let time_str = match t.meeting_tz { Some(tz) => format!("{}{} ", time_str, tz), None => time_str };
+		let tags_str = if t.tags.is_empty() { String::new() } else { format!("  {}", t.tags.join(" ")) };
+		println!("{} {}{}{}  {} {}", format!("^{}", r.id()).color(c.muted), time_str.color(c.accent), t.text.bold(), tags_str.color(c.tag).italic(), r.rule.to_string().dimmed(), format!("from {}", theme().date(r.start)).dimmed());
 	}
-	notes
+	Ok(())
 }
 
-fn parse_text_meta_only(rest: &str) -> (String, Option<u8>, Vec<String>) {
-	let mut text = rest.to_string();
-	let mut pr = None;
-	if let Some(stripped) = text.strip_prefix("(!!!) ") {
-		pr = Some(3);
-		text = stripped.to_string();
-	} else if let Some(stripped) = text.strip_prefix("(!!) ") {
-		pr = Some(2);
-		text = stripped.to_string();
-	} else if let Some(stripped) = text.strip_prefix("(!) ") {
-		pr = Some(1);
-		text = stripped.to_string();
+fn list_meetings(journal: &Journal, range: DateRange) -> Result<()> {
+	let midnight = range.from.and_time(NaiveTime::MIN);
+	let next = range.to.and_time(NaiveTime::MIN) + chrono::Days::new(1);
+	let meetings = journal.meetings_between(midnight, next)?;
+	let (t, c) = (theme(), &theme().colors);
+	if meetings.is_empty() {
+		let span = if range.is_single_day() { t.date(range.from) } else { format!("{} - {}", t.date(range.from), t.date(range.to)) };
+		println!("No meetings for {}", span);
+		return Ok(());
 	}
-	let mut tags = Vec::new();
-	let parts: Vec<&str> = text.split_whitespace().collect();
-	let mut kept: Vec<&str> = Vec::new();
-	for p in parts {
-		if let Some(t) = p.strip_prefix('#') {
-			if !t.is_empty() { tags.push(t.to_string()); }
-		} else {
-			kept.push(p);
-		}
+	for m in meetings {
+		let dur = (m.end - m.start).num_minutes();
+		let ends = if m.end > next { format!("  until {}", m.end.format("%a %H:%M")) } else { String::new() };
+		println!("{} {:>5} ({}m) {}{}{}", t.date(m.start.date()), m.start.format("%H:%M"), dur, m.bullet.text, meeting_zone(&m.bullet).color(c.muted), ends.color(c.muted));
 	}
-	let final_text = kept.join(" ");
-	(final_text, pr, tags)
+	Ok(())
 }
 
-fn parse_text_meeting_meta(rest: &str) -> (String, Option<u8>, Vec<String>, Option<NaiveTime>, Option<u32>) {
-	let mut remaining = rest.to_string();
-	let mut meeting_time: Option<NaiveTime> = None;
-	let mut duration: Option<u32> = None;
-	// Meeting prefix format: [mtg HH:MM] or [mtg HH:MM D]
-	if let Some(body) = remaining.strip_prefix("[mtg ") {
-		if let Some(close_idx) = body.find(']') {
-			let spec = &body[..close_idx];
-			let after = &body[close_idx+1..];
-			let parts: Vec<&str> = spec.split_whitespace().collect();
-			if !parts.is_empty() {
-				if let Ok(t) = NaiveTime::parse_from_str(parts[0], "%H:%M") { meeting_time = Some(t); }
-				if parts.len() > 1 { if let Ok(d) = parts[1].parse::<u32>() { duration = Some(d); } }
+fn free_slots(journal: &Journal, date: NaiveDate, from: NaiveTime, to: NaiveTime, min: u32) -> Result<()> {
+	let slots = journal.free_slots(date, from, to, min)?;
+	let (t, c) = (theme(), &theme().colors);
+	if slots.is_empty() { println!("{}", format!("No free slots of {}m on {}", min, t.date(date)).dimmed()); return Ok(()); }
+	for (start, end) in slots {
+		println!("{} {}-{} ({}m)", t.date(date), start.format("%H:%M").to_string().color(c.done), end.format("%H:%M").to_string().color(c.done), (end - start).num_minutes());
+	}
+	Ok(())
+}
+
+fn search(journal: &Journal, query: &Query) -> Result<()> {
+	let hits = journal.search(query)?;
+	if hits.is_empty() { println!("{}", "No matching bullets".dimmed()); return Ok(()); }
+	let c = &theme().colors;
+	for h in &hits {
+		let b = &h.bullet;
+		let checkbox = glyph(b);
+		let id = b.id.as_ref().map(|id| format!("^{}", id)).unwrap_or_else(|| format!("#{}", b.visible_index));
+		let time_str = meeting_clock(h.date, b).map(|t| format!("{} ", t)).unwrap_or_default();
+		let tags_str = if b.tags.is_empty() { String::new() } else { format!("  {}", b.tags.join(" ")) };
+		let text = bullet_text(b, false);
+		println!("{} {:<6} {} {}{}{}{}{}", page_label(h).color(c.accent), id.color(c.muted), checkbox, time_str.color(c.accent), text, tags_str.color(c.tag).italic(), meeting_zone(b).color(c.muted), links(b).color(c.muted));
+	}
+	println!("{}", format!("{} match(es)", hits.len()).dimmed());
+	Ok(())
+}
+
+/// Walk through the open bullets on `from` one at a time, then apply every
+/// decision together.
+fn review_migration(journal: &Journal, from: NaiveDate, to: NaiveDate) -> Result<()> {
+	if !io::stdin().is_terminal() { bail!("interactive migration needs a terminal"); }
+	let candidates = journal.migration_candidates(from)?;
+	if candidates.is_empty() { println!("No open bullets to migrate from {}", from); return Ok(()); }
+	println!("\n{}", format!("Migrating {} open bullet(s) from {}", candidates.len(), from).bold().underline());
+	println!("{}", format!("[f] forward to {}  [d] pick a date  [x] done  [c] cancel  [s] keep  [q] quit", to).dimmed());
+	let mut decisions = Vec::new();
+	for (i, b) in candidates.iter().enumerate() {
+		println!("\n{} {} {}", format!("{}/{}", i + 1, candidates.len()).color(theme().colors.muted), glyph(b), bullet_text(b, false));
+		for n in &b.notes { println!("      {}", n.dimmed()); }
+		let decision = loop {
+			print!("{} ", ">".color(theme().colors.accent));
+			io::stdout().flush()?;
+			match read_key()? {
+				'f' => break Decision::Forward(to),
+				'd' => match prompt_date(journal.clock().today())? {
+					Some(d) if d > from => break Decision::Schedule(d),
+					Some(_) => println!("{}", format!("Pick a date after {}", from).red()),
+					None => {}
+				},
+				'x' => break Decision::Done,
+				'c' => break Decision::Cancel,
+				's' => break Decision::Keep,
+				'q' => { println!("\n{}", "Migration aborted; nothing changed".color(theme().colors.warning)); return Ok(()); }
+				_ => println!("{}", "f, d, x, c, s or q".dimmed()),
 			}
-			remaining = after.trim_start().to_string();
-		}
+		};
+		println!("{}", describe(decision));
+		let id = b.id.clone().map(BulletRef::Id).unwrap_or(BulletRef::Index(b.visible_index));
+		decisions.push((id, decision));
 	}
-	let (text, pr, tags) = parse_text_meta_only(&remaining);
-	(text, pr, tags, meeting_time, duration)
+
+	let applied = journal.apply_migration(from, decisions)?;
+	println!("\n{}", "Summary".bold().underline());
+	for (b, d) in &applied {
+		println!("  {} {}", describe(*d), b.text);
+	}
+	let count = |f: fn(&Decision) -> bool| applied.iter().filter(|(_, d)| f(d)).count();
+	println!("{}", format!(
+		"{} forwarded, {} scheduled, {} done, {} cancelled, {} kept",
+		count(|d| matches!(d, Decision::Forward(_))),
+		count(|d| matches!(d, Decision::Schedule(_))),
+		count(|d| *d == Decision::Done),
+		count(|d| *d == Decision::Cancel),
+		count(|d| *d == Decision::Keep),
+	).color(theme().colors.done));
+	Ok(())
 }
 
-fn add_bullet(date: NaiveDate, text: &str, priority: Option<u8>, tags: &[String], notes: &[String]) -> Result<()> {
-	let path = file_for(date)?;
-	let mut lines = read_file_lines(&path)?;
-	let mut prefix = String::new();
-	match priority {
-		Some(3) => prefix.push_str("(!!!) "),
-		Some(2) => prefix.push_str("(!!) "),
-		Some(1) => prefix.push_str("(!) "),
-		_ => {}
+fn describe(d: Decision) -> ColoredString {
+	let (t, g, c) = (theme(), &theme().glyphs, &theme().colors);
+	match d {
+		Decision::Forward(to) => format!("{} {}", g.migrated, t.date(to)).color(c.accent),
+		Decision::Schedule(to) => format!("{} {}", g.scheduled, t.date(to)).color(c.event),
+		Decision::Done => format!("{} done", g.done).color(c.done),
+		Decision::Cancel => format!("{} cancelled", g.cancelled).color(c.cancelled),
+		Decision::Keep => format!("{} kept", g.open).normal(),
 	}
-	let mut suffix = String::new();
-	if !tags.is_empty() {
-		for t in tags { suffix.push_str(&format!(" #{}", t)); }
+}
+
+/// Read one key press without waiting for Enter. Esc and Ctrl-C read as `q`.
+fn read_key() -> Result<char> {
+	terminal::enable_raw_mode()?;
+	let key = loop {
+		match event::read() {
+			Ok(Event::Key(k)) if k.kind == KeyEventKind::Press => match k.code {
+				KeyCode::Char('c') if k.modifiers.contains(KeyModifiers::CONTROL) => break Ok('q'),
+				KeyCode::Char(c) => break Ok(c.to_ascii_lowercase()),
+				KeyCode::Esc => break Ok('q'),
+				_ => {}
+			},
+			Ok(_) => {}
+			Err(e) => break Err(e),
+		}
+	};
+	terminal::disable_raw_mode()?;
+	Ok(key?)
+}
+
+/// Ask for a date on its own line; an empty answer goes back to the choices.
+fn prompt_date(today: NaiveDate) -> Result<Option<NaiveDate>> {
+	print!("\ndate (YYYY-MM-DD, fri, +3d, ...): ");
+	io::stdout().flush()?;
+	let mut line = String::new();
+	io::stdin().read_line(&mut line)?;
+	let line = line.trim();
+	if line.is_empty() { return Ok(None); }
+	match parse_date_at(line, today) {
+		Ok(d) => Ok(Some(d)),
+		Err(e) => { println!("{}", e.to_string().red()); Ok(None) }
 	}
-	let new_line = format!("- [ ] {}{}{}", prefix, text.trim(), suffix);
-	lines.push(new_line);
-	for n in notes {
-		lines.push(format!("  - note: {}", n));
+}
+
+fn list_journals(config: &Config, current: &Path, source: Source) -> Result<()> {
+	for name in config.journals.keys() {
+		let path = config.journal_path(name)?;
+		let marker = if path == current { "*".color(theme().colors.done).bold() } else { " ".normal() };
+		let default = if config.default.as_deref() == Some(name.as_str()) { " (default)".dimmed().to_string() } else { String::new() };
+		println!("{} {:<12} {}{}", marker, name.bold(), path.display(), default);
 	}
-	write_file_lines(&path, &lines)?;
-	println!("Added to {}", path.display());
+	let why = match source {
+		Source::Flag => "--journal",
+		Source::Env => "BJ_DIR",
+		Source::Local => "project .bj/",
+		Source::Default => "config default",
+		Source::Platform => "platform data dir",
+	};
+	println!("{}", format!("Using {} (from {})", current.display(), why).dimmed());
 	Ok(())
 }
 
-fn add_meeting(date: NaiveDate, time: NaiveTime, duration_min: u32, title: &str, tags: &[String], notes: &[String]) -> Result<()> {
-	let mut mt_prefix = format!("[mtg {} {}] ", time.format("%H:%M"), duration_min);
-	let full = format!("{}{}", mt_prefix, title);
-	add_bullet(date, &full, None, tags, notes)
+/// Where a hit lives: its day, `YYYY-MM` for a month page, `@name` for a collection.
+fn page_label(h: &SearchHit) -> String {
+	match &h.page {
+		Page::Day => theme().date(h.date),
+		Page::Month => h.date.format("%Y-%m").to_string(),
+		Page::Collection(name) => format!("@{}", name),
+	}
 }
 
-fn list_meetings(date: NaiveDate) -> Result<()> {
-	let path = file_for(date)?;
-	let lines = read_file_lines(&path)?;
-	let mut bullets = parse_bullets(&lines)
-		.into_iter()
-		.filter(|b| b.meeting_time.is_some())
-		.collect::<Vec<_>>();
-	if bullets.is_empty() { println!("No meetings for {}", date); return Ok(()); }
-	bullets.sort_by_key(|b| b.meeting_time);
-	for b in bullets {
-		let t = b.meeting_time.unwrap();
-		let dur = b.meeting_duration_min.unwrap_or(60);
-		println!("{} {:>5} ({}m) {}", date, t.format("%H:%M"), dur, b.text);
+fn history(journal: &Journal, id: &str) -> Result<()> {
+	let hops = journal.history(id)?;
+	if hops.is_empty() { println!("{}", format!("No bullet ^{}", id).dimmed()); return Ok(()); }
+	println!("\n{}", format!("History of ^{}: {}", id, hops[hops.len() - 1].bullet.text).bold().underline());
+	let c = &theme().colors;
+	for (i, h) in hops.iter().enumerate() {
+		println!(" {} {} {} {}{}", branch(i, hops.len()), page_label(h).color(c.accent), glyph(&h.bullet), bullet_text(&h.bullet, false), links(&h.bullet).color(c.muted));
 	}
+	println!();
 	Ok(())
 }
 
-fn migrate_one(from: NaiveDate, to: NaiveDate, id: usize) -> Result<()> {
-	if from == to { bail!("from and to dates are the same; nothing to migrate"); }
-	let from_path = file_for(from)?;
-	let mut from_lines = read_file_lines(&from_path)?;
-	let bullets = parse_bullets(&from_lines);
-	let Some(target) = bullets.iter().find(|b| b.visible_index == id) else { bail!("bullet {} not found on {}", id, from) };
-	if target.completed { bail!("bullet {} is already completed", id); }
-	// reconstruct text without leading marker
-	let raw = from_lines[target.line_index].clone();
-	let text = raw.trim_start().trim_start_matches("- [ ] ").to_string();
-	let (text, pr, tags, mt, dur) = parse_text_meeting_meta(&text);
-	let mut full_text = String::new();
-	if let Some(t) = mt { full_text.push_str(&format!("[mtg {}{}] ", t.format("%H:%M"), dur.map(|d| format!(" {}", d)).unwrap_or_default())); }
-	full_text.push_str(&text);
-	add_bullet(to, &full_text, pr, &tags, &[])?;
-	from_lines.remove(target.line_index);
-	write_file_lines(&from_path, &from_lines)?;
-	println!("{}", format!("Migrated bullet {} from {} to {}", id, from, to).green());
+fn notify_upcoming_meetings(journal: &Journal, window_minutes: i64) -> Result<()> {
+	for m in journal.take_upcoming_meetings(window_minutes)? {
+		let t = meeting_clock(m.date, &m.bullet).unwrap();
+		let title = "Upcoming meeting";
+		let msg = format!("{} at {} (in {} min)", m.bullet.text, t, m.minutes_until);
+		if which::which("notify-send").is_ok() {
+			let _ = std::process::Command::new("notify-send").arg(title).arg(msg).status();
+		} else {
+			println!("{}: {}", title, msg);
+		}
+	}
 	Ok(())
 }
 
-// Backward compatibility wrapper
-fn migrate_one_to_today(from: NaiveDate, id: usize) -> Result<()> {
-	let to = Local::now().date_naive();
-	migrate_one(from, to, id)
+/// Signifier glyph shared by every view.
+fn glyph(b: &Bullet) -> ColoredString {
+	let (g, c) = (&theme().glyphs, &theme().colors);
+	match (b.kind, b.state) {
+		(Kind::Note, _) => g.note.color(c.muted),
+		(Kind::Event, _) => g.event.color(c.event),
+		(_, State::Open) => g.open.color(c.muted),
+		(_, State::Done) => g.done.color(c.done),
+		(_, State::Migrated) => g.migrated.color(c.migrated),
+		(_, State::Scheduled) => g.scheduled.color(c.migrated),
+		(_, State::Cancelled) => g.cancelled.color(c.cancelled),
+	}
 }
 
-fn notified_state_path() -> Result<PathBuf> { Ok(data_dir()?.join("notified.meetings")) }
-
-fn notify_upcoming_meetings(window_minutes: i64) -> Result<()> {
-	let today = Local::now().date_naive();
-	let now = Local::now().time();
-	let path = file_for(today)?;
-	let lines = read_file_lines(&path)?;
-	let bullets = parse_bullets(&lines);
-	let mut state_path = notified_state_path()?;
-	let mut sent = std::collections::HashSet::new();
-	if state_path.exists() {
-		let s = fs::read_to_string(&state_path).unwrap_or_default();
-		for line in s.lines() { sent.insert(line.to_string()); }
+fn priority_icon(b: &Bullet) -> ColoredString {
+	let ([high, medium, low, none], c) = (theme().glyphs.priority, &theme().colors);
+	match b.priority {
+		Some(3) => high.color(c.high),
+		Some(2) => medium.color(c.medium),
+		Some(1) => low.color(c.low),
+		_ => none.normal(),
 	}
-	let mut new_sent: Vec<String> = Vec::new();
-	for b in bullets {
-		let Some(t) = b.meeting_time else { continue };
-		let start_key = format!("{}|{}", today, t.format("%H:%M"));
-		if sent.contains(&start_key) { continue; }
-		let diff = (t - now).num_minutes();
-		if diff >= 0 && diff <= window_minutes {
-			let title = "Upcoming meeting";
-			let msg = format!("{} at {} (in {} min)", b.text, t.format("%H:%M"), diff);
-			if which::which("notify-send").is_ok() {
-				let _ = std::process::Command::new("notify-send").arg(title).arg(msg).status();
-			} else {
-				println!("{}: {}", title, msg);
-			}
-			new_sent.push(start_key);
-		}
+}
+
+/// Bullet text styled by state; `emphasis` bolds open tasks and events.
+fn bullet_text(b: &Bullet, emphasis: bool) -> ColoredString {
+	match (b.kind, b.state) {
+		(_, State::Done) | (_, State::Cancelled) => b.text.dimmed().strikethrough(),
+		(_, State::Migrated) | (_, State::Scheduled) => b.text.dimmed(),
+		(Kind::Note, _) => b.text.italic(),
+		_ if emphasis => b.text.bold(),
+		_ => b.text.normal(),
 	}
-	if !new_sent.is_empty() {
-		let mut contents = String::new();
-		for k in sent { contents.push_str(&format!("{}\n", k)); }
-		for k in new_sent { contents.push_str(&format!("{}\n", k)); }
-		fs::write(&state_path, contents).ok();
+}
+
+/// Local start time of a meeting on `date`, marked `+1`/`-1` when its zone
+/// puts it on a neighbouring local day.
+fn meeting_clock(date: NaiveDate, b: &Bullet) -> Option<String> {
+	let start = b.meeting_start(date)?;
+	let shift = (start.date() - date).num_days();
+	Some(format!("{}{}", start.format("%H:%M"), if shift == 0 { String::new() } else { format!("{:+}", shift) }))
+}
+
+/// The meeting time as written, for meetings in another zone.
+fn meeting_zone(b: &Bullet) -> String {
+	match (b.meeting_time, b.meeting_tz) {
+		(Some(t), Some(tz)) => format!("  ({} {})", t.format("%H:%M"), tz),
+		_ => String::new(),
 	}
-	Ok(())
 }
 
-fn list_bullets(date: NaiveDate, filter_tags: &[String], filter_priority: Option<u8>) -> Result<()> {
-	let path = file_for(date)?;
-	let lines = read_file_lines(&path)?;
-	let bullets = parse_bullets(&lines);
+/// Migration arrows (`← from`, `→ to`) shown after the bullet text; a
+/// month page shows as `YYYY-MM` and a collection as `@name`.
+fn links(b: &Bullet) -> String {
+	let t = theme();
+	let page = |day: Option<NaiveDate>, month: Option<NaiveDate>, collection: &Option<String>| day.map(|d| t.date(d))
+		.or(month.map(|m| m.format("%Y-%m").to_string()))
+		.or_else(|| collection.as_ref().map(|n| format!("@{}", n)));
+	let mut out = String::new();
+	if let Some(p) = page(b.migrated_from, b.migrated_from_month, &b.migrated_from_collection) { out.push_str(&format!("  {} {}", t.glyphs.from, p)); }
+	if let Some(p) = page(b.migrated_to, b.migrated_to_month, &b.migrated_to_collection) { out.push_str(&format!("  {} {}", t.glyphs.to, p)); }
+	out
+}
+
+/// Zone, migration links, recurrence and ID shown dimmed after a bullet.
+fn trailer(b: &Bullet) -> String {
+	let recurring = if b.recurring { format!("  {}", theme().glyphs.recurring) } else { String::new() };
+	format!("{}{}{}{}", meeting_zone(b), links(b), recurring, b.id.as_ref().map(|id| format!("  ^{}", id)).unwrap_or_default())
+}
+
+/// A horizontal rule of the frame between the `left` and `right` pieces.
+fn frame_line(left: &str, right: &str, width: usize) -> ColoredString {
+	let g = &theme().glyphs;
+	format!("{}{}{}", left, g.frame[4].repeat(width), right).color(theme().colors.muted)
+}
+
+/// Tree connector for note `i` of `count`.
+fn branch(i: usize, count: usize) -> ColoredString {
+	let g = &theme().glyphs;
+	(if i + 1 == count { g.last_branch } else { g.branch }).color(theme().colors.muted)
+}
+
+fn list_bullets(journal: &Journal, date: NaiveDate, filter_tags: &[String], filter_priority: Option<u8>) -> Result<()> {
+	let bullets = journal.bullets(date)?;
+	let (t, g, c) = (theme(), &theme().glyphs, &theme().colors);
 	
 	if bullets.is_empty() {
-		println!("\n{} {}", "📭".normal(), format!("No bullets for {}", date).dimmed());
+		println!("\n{}{}", g.empty, format!("No bullets for {}", t.date(date)).dimmed());
 		return Ok(());
 	}
 	
 	// Get today's date
-	let today = Local::now().date_naive();
+	let today = journal.clock().today();
 	
 	// Count tasks
-	let total = bullets.len();
-	let completed = bullets.iter().filter(|b| b.completed).count();
+	let total = bullets.iter().filter(|b| b.is_actionable()).count();
+	let completed = bullets.iter().filter(|b| b.is_done()).count();
 	let meetings = bullets.iter().filter(|b| b.meeting_time.is_some()).count();
 	
 	// Progress bar
 	let pct = if total > 0 { (completed as f64 / total as f64 * 100.0) as usize } else { 0 };
 	let bars = 20;
-	let filled = if total > 0 { (completed * bars) / total } else { 0 };
+	let filled = (completed * bars).checked_div(total).unwrap_or(0);
 	let empty = bars - filled;
-	let progress_bar = format!("{}{}", "━".repeat(filled).green(), "━".repeat(empty).bright_black());
+	let progress_bar = format!("{}{}", g.bar[0].repeat(filled).color(c.done), g.bar[1].repeat(empty).color(c.muted));
 	
 	// Box width
-	let box_width = 50;
+	let box_width = t.box_width;
+	let border = g.frame[5].color(c.muted);
 	
 	// Date string
 	let date_str = if date == today {
@@ -491,274 +1354,193 @@ fn list_bullets(date: NaiveDate, filter_tags: &[String], filter_priority: Option
 	} else if date == today.succ_opt().unwrap_or(date) {
 		"Tomorrow".to_string()
 	} else {
-		date.format("%A, %B %d").to_string()
+		date.format(&t.heading_format).to_string()
 	};
 	
 	// Header
-	println!("\n{}", format!("╭{:─<width$}╮", "", width = box_width).bright_black());
+	println!("\n{}", frame_line(g.frame[0], g.frame[1], box_width));
 	
 	// Title centered
 	let title = "BULLET JOURNAL";
 	let pad_left = (box_width - title.len()) / 2;
 	let pad_right = box_width - title.len() - pad_left;
-	println!("│{}{}{}│", 
+	println!("{}{}{}{}{}", 
+		border,
 		" ".repeat(pad_left),
-		title.bold().magenta(),
-		" ".repeat(pad_right)
+		title.bold().color(c.title),
+		" ".repeat(pad_right),
+		border
 	);
 	
-	println!("{}", format!("├{:─<width$}┤", "", width = box_width).bright_black());
+	println!("{}", frame_line(g.frame[6], g.frame[7], box_width));
 	
 	// Date line
-	let date_display = format!("📅 {}", date_str);
-	let date_len = date_display.chars().count(); 
+	let date_display = format!("{}{}", g.calendar, date_str);
+	let date_len = date_display.width(); 
 	let date_pad = if box_width > date_len { box_width - date_len - 2 } else { 0 };
-	println!("│ {}{} │", date_display.bold().cyan(), " ".repeat(date_pad));
+	println!("{} {}{} {}", border, date_display.bold().color(c.accent), " ".repeat(date_pad), border);
 	
 	// Stats line
 	let stats_display = format!("{} {}%", progress_bar, pct);
 	let stats_len = bars + 1 + pct.to_string().len() + 1; // bars + space + pct + %
 	let stats_pad = if box_width > stats_len { box_width - stats_len - 2 } else { 0 };
-	println!("│ {}{} │", stats_display, " ".repeat(stats_pad));
+	println!("{} {}{} {}", border, stats_display, " ".repeat(stats_pad), border);
 	
-	let summary = format!("✓ {}/{} done  •  🗓 {} mtgs", completed, total, meetings);
-	let sum_len = summary.chars().count();
+	let summary = format!("{}{}/{} done  {}  {}{} mtgs", g.check, completed, total, g.dot, g.meetings, meetings);
+	let sum_len = summary.width();
 	let sum_pad = if box_width > sum_len { box_width - sum_len - 2 } else { 0 };
-	println!("│ {}{} │", summary.italic().dimmed(), " ".repeat(sum_pad));
+	println!("{} {}{} {}", border, summary.italic().dimmed(), " ".repeat(sum_pad), border);
 	
-	println!("{}", format!("╰{:─<width$}╯", "", width = box_width).bright_black());
+	println!("{}", frame_line(g.frame[2], g.frame[3], box_width));
 	println!();
 	
 	for b in bullets {
-		if let Some(p) = filter_priority { if b.priority != Some(p) { continue; } }
-		if !filter_tags.is_empty() {
-			if !filter_tags.iter().all(|t| b.tags.iter().any(|bt| bt == t)) { continue; }
-		}
+		if !b.matches(filter_tags, filter_priority) { continue; }
 		
 		// Fancy Checkbox
-		let checkbox = if b.completed { "●".green() } else { "○".bright_black() };
+		let checkbox = glyph(&b);
 		
 		// Priority with different style
-		let priority_icon = match b.priority {
-			Some(3) => "▲".red(),
-			Some(2) => "▵".yellow(),
-			Some(1) => "▽".green(),
-			_ => " ".normal(),
-		};
+		let priority_icon = priority_icon(&b);
 		
 		// Time with clock icon
-		let time_str = if let Some(t) = b.meeting_time {
-			format!("{} {}", "🕒".cyan(), t.format("%H:%M").to_string().cyan())
+		let time_str = if let Some(time) = meeting_clock(date, &b) {
+			format!("{}{}", g.clock.color(c.accent), time.color(c.accent))
 		} else {
-			"        ".normal().to_string()
+			" ".repeat(g.clock.width() + 5)
 		};
 		
 		// Tags as badges
 		let tags_str = if b.tags.is_empty() { String::new() } else { 
-			format!(" {}", b.tags.iter().map(|t| format!("{}", t)).collect::<Vec<_>>().join(" "))
+			format!(" {}", b.tags.join(" "))
 		};
 		
 		let idx = format!("{:>2}", b.visible_index).dimmed();
-		let text = if b.completed { b.text.dimmed().strikethrough() } else { b.text.bold() };
+		let text = bullet_text(&b, true);
+		let id_str = trailer(&b);
 		
 		// Main line
-		println!(" {} {} {} {} {}{}", 
+		println!(" {} {} {} {} {}{}{}", 
 			idx, 
 			checkbox, 
 			priority_icon, 
 			time_str, 
 			text, 
-			if b.tags.is_empty() { "".normal() } else { format!("  {}", tags_str).blue().italic() }
+			if b.tags.is_empty() { "".normal() } else { format!("  {}", tags_str).color(c.tag).italic() },
+			id_str.color(c.muted)
 		);
 		
 		// Notes with nice tree structure
-		let last_note_idx = b.notes.len().saturating_sub(1);
 		for (i, n) in b.notes.iter().enumerate() {
-			let connector = if i == last_note_idx { "└──" } else { "├──" };
-			println!("       {} {}", connector.bright_black(), n.dimmed());
+			println!("       {} {}", branch(i, b.notes.len()), n.dimmed());
 		}
 	}
 	println!();
 	Ok(())
 }
 
-fn mark_done(date: NaiveDate, id: usize) -> Result<()> {
-	let path = file_for(date)?;
-	let mut lines = read_file_lines(&path)?;
-	let bullets = parse_bullets(&lines);
-	let Some(target) = bullets.iter().find(|b| b.visible_index == id) else { bail!("bullet {} not found", id) };
-	let raw = &lines[target.line_index];
-	let replaced = if raw.trim_start().starts_with("- [ ] ") {
-		raw.replacen("- [ ] ", "- [x] ", 1)
-	} else if raw.trim_start().starts_with("- [x] ") {
-		raw.to_string()
-	} else {
-		raw.to_string()
-	};
-	lines[target.line_index] = replaced;
-	write_file_lines(&path, &lines)?;
-	println!("Marked done: {} #{}", date, id);
-	Ok(())
-}
-
-fn delete_bullet(date: NaiveDate, id: usize) -> Result<()> {
-	let path = file_for(date)?;
-	let mut lines = read_file_lines(&path)?;
-	let bullets = parse_bullets(&lines);
-	let Some(target) = bullets.iter().find(|b| b.visible_index == id) else { bail!("bullet {} not found", id) };
-	
-	// Get the text for confirmation message before deleting
-	let bullet_text = target.text.clone();
-	
-	// Remove the bullet line and any associated note lines
-	let mut lines_to_remove = vec![target.line_index];
+fn week_view(journal: &Journal, base: NaiveDate, filter_tags: &[String], filter_priority: Option<u8>) -> Result<()> {
+	let start = theme().week_of(base);
 	
-	// Find note lines that belong to this bullet (indented lines immediately following)
-	for i in (target.line_index + 1)..lines.len() {
-		let line = &lines[i];
-		if line.trim().is_empty() {
-			// Empty line might separate bullets, keep looking
-			continue;
-		} else if line.starts_with("  ") && !line.trim_start().starts_with("- ") {
-			// This is an indented note line
-			lines_to_remove.push(i);
-		} else {
-			// Hit the next bullet or non-note content
-			break;
-		}
-	}
+	// Header for the week
+	let end = start + chrono::Days::new(6);
+	println!("\n{}", format!("Week: {} - {}", theme().date(start), theme().date(end)).bold().underline());
 	
-	// Remove lines in reverse order to maintain indices
-	for &idx in lines_to_remove.iter().rev() {
-		lines.remove(idx);
+	for i in 0..7 {
+		let day = start + chrono::Days::new(i);
+		let rows = day_rows(journal, day)?;
+		print_day(day, journal.clock().today(), &rows, filter_tags, filter_priority);
 	}
-	
-	write_file_lines(&path, &lines)?;
-	println!("Deleted: {} #{} - \"{}\"", date, id, bullet_text);
+	println!();
 	Ok(())
 }
 
-fn migrate_open(from: NaiveDate, to: NaiveDate) -> Result<()> {
-	if from == to { bail!("from and to dates are the same; nothing to migrate"); }
-	let from_path = file_for(from)?;
-	let mut from_lines = read_file_lines(&from_path)?;
-	let bullets = parse_bullets(&from_lines);
-	let mut moved_any = false;
-	for b in bullets.into_iter().rev() { // reverse so removals do not shift earlier indexes
-		if !b.completed {
-			let raw = from_lines[b.line_index].clone();
-			let text = raw.trim_start().trim_start_matches("- [ ] ").to_string();
-			let (text, pr, tags, mt, dur) = parse_text_meeting_meta(&text);
-			// Preserve meeting marker if present by reconstructing text with meeting prefix
-			let mut full_text = String::new();
-			if let Some(t) = mt { full_text.push_str(&format!("[mtg {}{}] ", t.format("%H:%M"), dur.map(|d| format!(" {}", d)).unwrap_or_default())); }
-			full_text.push_str(&text);
-			add_bullet(to, &full_text, pr, &tags, &[])?;
-			from_lines.remove(b.line_index);
-			moved_any = true;
-		}
-	}
-	write_file_lines(&from_path, &from_lines)?;
-	if moved_any {
-		println!("Migrated open bullets from {} to {}", from, to);
-	} else {
-		println!("No open bullets to migrate from {}", from);
+/// Several days merged into one list, leaving out days with nothing to show.
+fn range_view(journal: &Journal, range: DateRange, filter_tags: &[String], filter_priority: Option<u8>) -> Result<()> {
+	let t = theme();
+	println!("\n{}", format!("{} - {}", t.date(range.from), t.date(range.to)).bold().underline());
+	let mut shown = 0;
+	for day in range.days() {
+		let rows = day_rows(journal, day)?;
+		if !rows.iter().any(|(_, b)| b.matches(filter_tags, filter_priority)) { continue; }
+		print_day(day, journal.clock().today(), &rows, filter_tags, filter_priority);
+		shown += 1;
 	}
+	if shown == 0 { println!("   {}", "No tasks".dimmed().italic()); }
+	println!();
 	Ok(())
 }
 
-// Backward compatibility wrapper
-fn migrate_open_to_today(from: NaiveDate) -> Result<()> {
-	let to = Local::now().date_naive();
-	migrate_open(from, to)
-}
-
-fn parse_priority_opt(v: Option<&str>) -> Result<Option<u8>> {
-	match v {
-		None => Ok(None),
-		Some(s) => {
-			let s = s.to_lowercase();
-			let p = match s.as_str() {
-				"3" | "high" | "h" => 3,
-				"2" | "med" | "m" | "medium" => 2,
-				"1" | "low" | "l" => 1,
-				_ => bail!("invalid priority: {}", s),
-			};
-			Ok(Some(p))
+/// What a day shows in multi-day views, with the time column of each row.
+fn day_rows(journal: &Journal, day: NaiveDate) -> Result<Vec<(Option<String>, Bullet)>> {
+	let g = &theme().glyphs;
+	let midnight = day.and_time(NaiveTime::MIN);
+	let meetings = journal.meetings_between(midnight, midnight + chrono::Days::new(1))?;
+	// Meetings are listed on the local day they start; ones still running
+	// from an earlier day are shown first with their end time.
+	let mut rows: Vec<(Option<String>, Bullet)> = meetings.iter()
+		.filter(|m| m.start < midnight)
+		.map(|m| (Some(format!("{}{}", g.continues, m.end.format("%H:%M"))), m.bullet.clone()))
+		.collect();
+	for b in journal.bullets(day)? {
+		match b.meeting_start(day) {
+			Some(s) if s.date() != day && !matches!(b.state, State::Migrated | State::Scheduled) => {}
+			_ => rows.push((meeting_clock(day, &b), b)),
 		}
 	}
+	rows.extend(meetings.iter()
+		.filter(|m| m.date != day && m.start >= midnight)
+		.map(|m| (Some(m.start.format("%H:%M").to_string()), m.bullet.clone())));
+	Ok(rows)
 }
 
-fn week_view(base: NaiveDate, filter_tags: &[String], filter_priority: Option<u8>) -> Result<()> {
-	let weekday = base.weekday().num_days_from_monday() as i64;
-	let start = base - chrono::Days::new(weekday as u64);
+fn print_day(day: NaiveDate, today: NaiveDate, rows: &[(Option<String>, Bullet)], filter_tags: &[String], filter_priority: Option<u8>) {
+	let (t, g, c) = (theme(), &theme().glyphs, &theme().colors);
+	let is_today = day == today;
+	let day_header = format!("{}", day.format(&t.day_format));
 	
-	// Header for the week
-	let end = start + chrono::Days::new(6);
-	println!("\n{}", format!("Week: {} - {}", start.format("%b %d"), end.format("%b %d")).bold().underline());
+	// Day header with separator
+	if is_today {
+		println!("\n{} {}", g.today.color(c.accent), day_header.bold().black().on_color(c.accent));
+	} else {
+		println!("\n{} {}", g.day.color(c.muted), day_header.bold().color(c.accent));
+	}
 	
-	for i in 0..7 {
-		let day = start + chrono::Days::new(i);
-		let path = file_for(day)?;
-		let lines = read_file_lines(&path)?;
-		let bullets = parse_bullets(&lines);
+	if rows.is_empty() {
+		println!("   {}", "No tasks".dimmed().italic());
+		return;
+	}
+	
+	for (time, b) in rows {
+		if !b.matches(filter_tags, filter_priority) { continue; }
 		
-		let is_today = day == Local::now().date_naive();
-		let day_header = format!("{}", day.format("%A, %b %d"));
+		let checkbox = glyph(b);
+		let priority_icon = priority_icon(b);
 		
-		// Day header with separator
-		if is_today {
-			println!("\n{} {}", "●".cyan(), day_header.bold().black().on_cyan());
+		let time_str = if let Some(time) = time {
+			format!("{} ", time).color(c.accent).to_string()
 		} else {
-			println!("\n{} {}", "○".bright_black(), day_header.bold().cyan());
-		}
+			"      ".normal().to_string()
+		};
 		
-		if bullets.is_empty() {
-			println!("   {}", "No tasks".dimmed().italic());
-			continue;
-		}
+		let tags_str = if b.tags.is_empty() { String::new() } else { 
+			format!(" {}", b.tags.join(" "))
+		};
 		
-		for b in bullets {
-			if let Some(p) = filter_priority { if b.priority != Some(p) { continue; } }
-			if !filter_tags.is_empty() {
-				if !filter_tags.iter().all(|t| b.tags.iter().any(|bt| bt == t)) { continue; }
-			}
-			
-			let checkbox = if b.completed { "●".green() } else { "○".bright_black() };
-			let priority_icon = match b.priority {
-				Some(3) => "▲".red(),
-				Some(2) => "▵".yellow(),
-				Some(1) => "▽".green(),
-				_ => " ".normal(),
-			};
-			
-			let time_str = if let Some(t) = b.meeting_time {
-				format!("{} ", t.format("%H:%M")).cyan().to_string()
-			} else {
-				"      ".normal().to_string()
-			};
-			
-			let tags_str = if b.tags.is_empty() { String::new() } else { 
-				format!(" {}", b.tags.iter().map(|t| format!("{}", t)).collect::<Vec<_>>().join(" "))
-			};
-			
-			let text = if b.completed { b.text.dimmed().strikethrough() } else { b.text.normal() };
-			
-			println!("   {} {} {} {}{}", checkbox, priority_icon, time_str, text, if b.tags.is_empty() { "".normal() } else { format!("  {}", tags_str).blue().italic() });
-			
-			let last_note_idx = b.notes.len().saturating_sub(1);
-			for (i, n) in b.notes.iter().enumerate() {
-				let connector = if i == last_note_idx { "└──" } else { "├──" };
-				println!("         {} {}", connector.bright_black(), n.dimmed());
-			}
+		let text = bullet_text(b, false);
+		let id_str = trailer(b);
+		
+		println!("   {} {} {} {}{}{}", checkbox, priority_icon, time_str, text, if b.tags.is_empty() { "".normal() } else { format!("  {}", tags_str).color(c.tag).italic() }, id_str.color(c.muted));
+		
+		for (i, n) in b.notes.iter().enumerate() {
+			println!("         {} {}", branch(i, b.notes.len()), n.dimmed());
 		}
 	}
-	println!();
-	Ok(())
 }
 
-fn month_calendar(base: NaiveDate) -> Result<()> {
-	let today = Local::now().date_naive();
+fn month_calendar(journal: &Journal, base: NaiveDate) -> Result<()> {
+	let (t, g, c) = (theme(), &theme().glyphs, &theme().colors);
+	let today = journal.clock().today();
 	let first = NaiveDate::from_ymd_opt(base.year(), base.month(), 1).context("invalid month")?;
 	let next_month = if base.month() == 12 { 
 		NaiveDate::from_ymd_opt(base.year()+1, 1, 1).unwrap() 
@@ -770,46 +1552,54 @@ fn month_calendar(base: NaiveDate) -> Result<()> {
 	let month_name = base.format("%B").to_string();
 	let header_text = format!("{} {}", month_name, base.year());
 	
-	// Width calculation: 7 days * 6 chars + 2 border = 44
-	let width = 42;
+	// 7 days * 6 chars, centred in a frame of at least box_width
+	let grid = 42;
+	let width = t.box_width.max(grid);
 	let pad_left = (width - header_text.len()) / 2;
 	let pad_right = width - header_text.len() - pad_left;
+	let border = g.frame[5].color(c.muted);
+	let (margin_left, margin_right) = (" ".repeat((width - grid) / 2), " ".repeat(width - grid - (width - grid) / 2));
+	let today_style = |s: String| s.bold().black().on_color(c.accent);
 	
-	println!("\n{}", format!("╭{:─<width$}╮", "", width = width).bright_black());
-	println!("│{}{}{}│", " ".repeat(pad_left), header_text.bold().cyan(), " ".repeat(pad_right));
-	println!("{}", format!("├{:─<width$}┤", "", width = width).bright_black());
+	println!("\n{}", frame_line(g.frame[0], g.frame[1], width));
+	println!("{}{}{}{}{}", border, " ".repeat(pad_left), header_text.bold().color(c.accent), " ".repeat(pad_right), border);
+	println!("{}", frame_line(g.frame[6], g.frame[7], width));
 	
-	// Correctly spaced header: 6 chars per day
-	println!("│{:^6}{:^6}{:^6}{:^6}{:^6}{:^6}{:^6}│", 
-		"Mo".bold(), "Tu".bold(), "We".bold(), "Th".bold(), 
-		"Fr".bold(), "Sa".bold().bright_blue(), "Su".bold().bright_blue());
+	// Correctly spaced header: 6 chars per day, starting on the configured day
+	let weekend = |d: Weekday| matches!(d, Weekday::Sat | Weekday::Sun);
+	print!("{}{}", border, margin_left);
+	for day in std::iter::successors(Some(t.week_start), |d| Some(d.succ())).take(7) {
+		let name = &day.to_string()[..2];
+		let name = if weekend(day) { name.bold().color(c.weekend) } else { name.bold() };
+		print!("{:^6}", name);
+	}
+	println!("{}{}", margin_right, border);
 	
-	let offset = first.weekday().num_days_from_monday();
+	let offset = first.weekday().days_since(t.week_start);
 	let mut col = 0;
 	
-	print!("│");
+	print!("{}{}", border, margin_left);
 	for _ in 0..offset { print!("      "); col += 1; }
 	
 	let mut d = 1u32;
 	while d <= last_day {
 		let cur = NaiveDate::from_ymd_opt(base.year(), base.month(), d).unwrap();
-		let path = file_for(cur)?;
-		let lines = read_file_lines(&path)?;
-		let bullets = parse_bullets(&lines);
+		let bullets = journal.bullets(cur)?;
 		
-		let has_meeting = bullets.iter().any(|b| b.meeting_time.is_some());
-		let has_open = bullets.iter().any(|b| !b.completed);
-		let all_done = !bullets.is_empty() && bullets.iter().all(|b| b.completed);
+		// Cancelled meetings don't take place, and migrated or scheduled ones are pointers to their new day.
+		let has_meeting = bullets.iter().any(|b| b.meeting_time.is_some() && !matches!(b.state, State::Cancelled | State::Migrated | State::Scheduled));
+		let has_open = bullets.iter().any(|b| b.is_open());
+		let all_done = bullets.iter().any(|b| b.is_done()) && !has_open;
 		
-		let marker = if has_meeting { "•".red() }
-		else if has_open { "•".yellow() }
-		else if all_done { "•".green() }
+		let marker = if has_meeting { g.dot.color(c.high) }
+		else if has_open { g.dot.color(c.medium) }
+		else if all_done { g.dot.color(c.done) }
 		else { " ".normal() };
 		
 		let day_str = if cur == today {
-			format!("{:>2}", d).bold().white().on_blue()
-		} else if cur.weekday().number_from_monday() >= 6 {
-			format!("{:>2}", d).bright_blue()
+			today_style(format!("{:>2}", d))
+		} else if weekend(cur.weekday()) {
+			format!("{:>2}", d).color(c.weekend)
 		} else {
 			format!("{:>2}", d).normal()
 		};
@@ -818,8 +1608,8 @@ fn month_calendar(base: NaiveDate) -> Result<()> {
 		col += 1;
 		
 		if col == 7 {
-			println!("│");
-			if d < last_day { print!("│"); }
+			println!("{}{}", margin_right, border);
+			if d < last_day { print!("{}{}", border, margin_left); }
 			col = 0;
 		}
 		d += 1;
@@ -827,452 +1617,19 @@ fn month_calendar(base: NaiveDate) -> Result<()> {
 	
 	if col > 0 {
 		while col < 7 { print!("      "); col += 1; }
-		println!("│");
+		println!("{}{}", margin_right, border);
 	}
 	
-	println!("{}", format!("╰{:─<width$}╯", "", width = width).bright_black());
+	println!("{}", frame_line(g.frame[2], g.frame[3], width));
+	
+	let collection = journal.month_bullets(first)?;
+	if !collection.is_empty() { month_log(first, &collection); }
 	
 	// Legend
 	println!("\n {}", "Legend:".bold().underline());
-	println!("  {} Meeting   {} Open task", "•".red(), "•".yellow());
-	println!("  {} All done  {} Today", "•".green(), "12".bold().white().on_blue());
+	println!("  {} Meeting   {} Open task", g.dot.color(c.high), g.dot.color(c.medium));
+	println!("  {} All done  {} Today", g.dot.color(c.done), today_style("12".to_string()));
 	println!();
 	
 	Ok(())
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::fs;
-    use std::env;
-    use serial_test::serial;
-    use std::sync::atomic::{AtomicUsize, Ordering};
-
-    static TEST_COUNTER: AtomicUsize = AtomicUsize::new(0);
-
-    struct TestEnv {
-        root: std::path::PathBuf,
-        data_dir: std::path::PathBuf,
-        _prev_xdg: Option<String>,
-    }
-    
-    impl TestEnv {
-        fn new() -> Self {
-            // Save current XDG_DATA_HOME
-            let prev_xdg = env::var("XDG_DATA_HOME").ok();
-            
-            // Create unique test directory using test counter
-            let mut test_root = env::temp_dir();
-            let test_num = TEST_COUNTER.fetch_add(1, Ordering::SeqCst);
-            let uniq = format!("bj_test_{}_{}", 
-                std::process::id(),
-                test_num);
-            test_root.push(uniq);
-            fs::create_dir_all(&test_root).expect("create test root");
-            
-            // Create XDG data dir inside test root
-            let data_home = test_root.join("data");
-            let data_dir = data_home.join("bullet_journal");
-            fs::create_dir_all(&data_dir).expect("create data dir");
-            
-            // Set XDG_DATA_HOME to our test directory
-            env::set_var("XDG_DATA_HOME", data_home.to_str().unwrap());
-            
-            TestEnv {
-                root: test_root,
-                data_dir,
-                _prev_xdg: prev_xdg,
-            }
-        }
-    }
-    
-    impl Drop for TestEnv {
-        fn drop(&mut self) {
-            // Restore previous XDG_DATA_HOME
-            match &self._prev_xdg {
-                Some(prev) => env::set_var("XDG_DATA_HOME", prev),
-                None => env::remove_var("XDG_DATA_HOME"),
-            }
-            // Clean up test directory
-            fs::remove_dir_all(&self.root).ok();
-        }
-    }
-
-    #[test]
-    fn test_parse_text_meta_only() {
-        // Test priority and tags
-        let s = "(!!!) Test bullet #work #urgent";
-        let (text, pr, tags) = parse_text_meta_only(s);
-        assert_eq!(text, "Test bullet", "Text not correctly extracted");
-        assert_eq!(pr, Some(3), "High priority not detected");
-        assert_eq!(tags, vec!["work".to_string(), "urgent".to_string()], "Tags not correctly parsed");
-
-        // Test medium priority
-        let s = "(!!) Medium priority #dev";
-        let (text, pr, tags) = parse_text_meta_only(s);
-        assert_eq!(text, "Medium priority", "Text with medium priority not extracted");
-        assert_eq!(pr, Some(2), "Medium priority not detected");
-        assert_eq!(tags, vec!["dev".to_string()], "Single tag not parsed");
-
-        // Test no metadata
-        let s = "Simple bullet";
-        let (text, pr, tags) = parse_text_meta_only(s);
-        assert_eq!(text, "Simple bullet", "Plain text not preserved");
-        assert_eq!(pr, None, "Should have no priority");
-        assert!(tags.is_empty(), "Should have no tags");
-    }
-
-    #[test]
-    fn test_parse_text_meeting_meta() {
-        // Test full meeting metadata
-        let s = "[mtg 15:30 45] Team sync #work";
-        let (text, pr, tags, mt, dur) = parse_text_meeting_meta(s);
-        assert_eq!(text, "Team sync", "Meeting text not extracted");
-        assert_eq!(pr, None, "Should have no priority");
-        assert_eq!(tags, vec!["work".to_string()], "Meeting tag not parsed");
-        assert_eq!(mt.unwrap().format("%H:%M").to_string(), "15:30", "Meeting time not parsed");
-        assert_eq!(dur, Some(45), "Meeting duration not parsed");
-
-        // Test meeting without duration
-        let s = "[mtg 09:00] Daily standup";
-        let (text, _pr, _tags, mt, dur) = parse_text_meeting_meta(s);
-        assert_eq!(text, "Daily standup", "Simple meeting text not extracted");
-        assert_eq!(mt.unwrap().format("%H:%M").to_string(), "09:00", "Simple meeting time not parsed");
-        assert_eq!(dur, None, "Should have no duration");
-
-        // Test non-meeting text
-        let s = "Regular bullet";
-        let (text, _pr, _tags, mt, dur) = parse_text_meeting_meta(s);
-        assert_eq!(text, "Regular bullet", "Non-meeting text should be preserved");
-        assert!(mt.is_none(), "Non-meeting should have no time");
-        assert!(dur.is_none(), "Non-meeting should have no duration");
-    }
-
-    #[test]
-    #[serial]  // Prevent parallel test runs
-    fn test_meeting_metadata() -> Result<()> {
-        let _env = TestEnv::new();
-        let date = NaiveDate::from_ymd_opt(2025, 11, 6).unwrap();
-        
-        // Add a meeting
-        add_meeting(date, 
-            NaiveTime::from_hms_opt(14, 30, 0).unwrap(),
-            45,
-            "Team Sync",
-            &vec!["work".to_string()],
-            &vec!["Prep required".to_string()]
-        )?;
-        
-        // Verify the meeting was added correctly
-        let path = file_for(date)?;
-        let lines = read_file_lines(&path)?;
-        let bullets = parse_bullets(&lines);
-        assert_eq!(bullets.len(), 1, "Expected exactly one meeting bullet");
-        
-        let mtg = &bullets[0];
-        assert_eq!(mtg.text, "Team Sync", "Meeting title mismatch");
-        assert_eq!(mtg.meeting_time.unwrap().format("%H:%M").to_string(), "14:30", "Meeting time mismatch");
-        assert_eq!(mtg.meeting_duration_min, Some(45), "Meeting duration mismatch");
-        assert_eq!(mtg.tags, vec!["work"], "Meeting tag mismatch");
-        assert_eq!(mtg.notes, vec!["Prep required"], "Meeting note mismatch");
-        
-        Ok(())
-    }
-
-    
-
-    #[test]
-    #[serial]  // Prevent parallel test runs 
-    fn test_add_and_parse_bullet() -> Result<()> {
-        let _env = TestEnv::new();
-        let date = NaiveDate::from_ymd_opt(2025, 11, 6).unwrap();
-        
-        // Add a bullet with priority, tags, and notes
-        add_bullet(date, "Write tests", Some(2), &vec!["dev".to_string()], &vec!["first note".to_string()])?;
-        
-        let path = file_for(date)?;
-        let lines = read_file_lines(&path)?;
-        let bullets = parse_bullets(&lines);
-        
-        assert_eq!(bullets.len(), 1, "Expected exactly one bullet");
-        let b = &bullets[0];
-        assert_eq!(b.text, "Write tests", "Bullet text mismatch");
-        assert_eq!(b.priority, Some(2), "Priority mismatch");
-        assert_eq!(b.tags, vec!["dev"], "Tags mismatch");
-        assert_eq!(b.notes.len(), 1, "Expected one note");
-        assert!(b.notes[0].contains("first note"), "Note content mismatch");
-        
-        Ok(())
-    }
-
-    #[test]
-    #[serial]  // Prevent parallel test runs
-    fn test_mark_done() -> Result<()> {
-        let _env = TestEnv::new();
-        let date = NaiveDate::from_ymd_opt(2025, 11, 6).unwrap();
-        
-        // Add two bullets
-        add_bullet(date, "Task A", None, &vec![], &vec![])?;
-        add_bullet(date, "Task B", None, &vec![], &vec![])?;
-        
-        // Parse to verify initial state
-        let initial = parse_bullets(&read_file_lines(&file_for(date)?)?);
-        assert_eq!(initial.len(), 2, "Expected two bullets initially");
-        assert!(!initial[0].completed && !initial[1].completed, "Bullets should start incomplete");
-        
-        // Mark first one done
-        mark_done(date, 1)?;
-        
-        let bullets = parse_bullets(&read_file_lines(&file_for(date)?)?);
-        assert_eq!(bullets.len(), 2, "Should still have two bullets after marking one done");
-        assert!(bullets[0].completed, "First bullet should be marked done");
-        assert!(!bullets[1].completed, "Second bullet should still be incomplete");
-        
-        Ok(())
-    }
-
-    #[test]
-    #[serial]  // Prevent parallel test runs
-    fn test_migrate_one_to_today() -> Result<()> {
-        let _env = TestEnv::new();
-        let from = NaiveDate::from_ymd_opt(2025, 11, 4).unwrap();
-        let today = Local::now().date_naive();
-        
-        // Create two bullets on source date with unique identifiable text
-        add_bullet(from, "Source Bullet A", None, &vec![], &vec![])?;
-        add_bullet(from, "Source Bullet B", Some(2), &vec!["important".to_string()], &vec![])?;
-        
-        // Read source file to find bullet indices
-        let from_path = file_for(from)?;
-        let initial_lines = read_file_lines(&from_path)?;
-        let initial = parse_bullets(&initial_lines);
-        assert_eq!(initial.len(), 2, "Should have two bullets initially");
-        
-        // Find B's index and migrate it
-        let b_index = initial.iter()
-            .find(|b| b.text == "Source Bullet B")
-            .map(|b| b.visible_index)
-            .expect("Should find bullet B");
-        migrate_one_to_today(from, b_index)?;
-        
-        // Verify source file - should only have bullet A
-        let source_after = parse_bullets(&read_file_lines(&from_path)?);
-        assert_eq!(source_after.len(), 1, "Source should have one bullet remaining");
-        assert_eq!(source_after[0].text, "Source Bullet A", "Wrong bullet removed from source");
-        
-        // Verify target file - should have bullet B with metadata
-        let today_path = file_for(today)?;
-        let target = parse_bullets(&read_file_lines(&today_path)?);
-        assert_eq!(target.len(), 1, "Target should have one bullet");
-        
-        let migrated = &target[0];
-        assert_eq!(migrated.text, "Source Bullet B", "Wrong bullet migrated");
-        assert_eq!(migrated.priority, Some(2), "Priority not preserved");
-        assert_eq!(migrated.tags, vec!["important"], "Tags not preserved");
-        
-        Ok(())
-    }
-
-    #[test]
-    #[serial]  // Prevent parallel test runs
-    fn test_migrate_open_to_today() -> Result<()> {
-        let _env = TestEnv::new();
-        let from = NaiveDate::from_ymd_opt(2025, 11, 3).unwrap();
-        let today = Local::now().date_naive();
-        
-        // Add three bullets with unique identifiable text
-        add_bullet(from, "First Task (Done)", None, &vec![], &vec![])?;
-        add_bullet(from, "Second Task (Open)", Some(1), &vec!["tag1".to_string()], &vec![])?;
-        add_bullet(from, "Third Task (Open)", Some(3), &vec!["tag2".to_string()], &vec![])?;
-        
-        // Mark first task done
-        let from_path = file_for(from)?;
-        let initial = parse_bullets(&read_file_lines(&from_path)?);
-        let first_id = initial.iter()
-            .find(|b| b.text == "First Task (Done)")
-            .map(|b| b.visible_index)
-            .expect("Should find first task");
-        mark_done(from, first_id)?;
-        
-        // Migrate open tasks
-        migrate_open_to_today(from)?;
-        
-        // Verify source - should only have done task
-        let source_after = parse_bullets(&read_file_lines(&from_path)?);
-        assert_eq!(source_after.len(), 1, "Source should have one bullet");
-        assert!(source_after[0].completed, "Source bullet should be done");
-        assert_eq!(source_after[0].text, "First Task (Done)", "Wrong task in source");
-        
-        // Verify target - should have both open tasks
-        let today_path = file_for(today)?;
-        let target = parse_bullets(&read_file_lines(&today_path)?);
-        assert_eq!(target.len(), 2, "Target should have two bullets");
-        
-        let second = target.iter()
-            .find(|b| b.text == "Second Task (Open)")
-            .expect("Second task should be migrated");
-        assert_eq!(second.priority, Some(1), "Priority not preserved");
-        assert_eq!(second.tags, vec!["tag1"], "Tags not preserved");
-        
-        let third = target.iter()
-            .find(|b| b.text == "Third Task (Open)")
-            .expect("Third task should be migrated");
-        assert_eq!(third.priority, Some(3), "Priority not preserved");
-        assert_eq!(third.tags, vec!["tag2"], "Tags not preserved");
-        
-        Ok(())
-    }
-
-    #[test]
-    #[serial]  // Prevent parallel test runs
-    fn test_delete_bullet() -> Result<()> {
-        let _env = TestEnv::new();
-        let date = NaiveDate::from_ymd_opt(2025, 11, 6).unwrap();
-        
-        // Add three bullets
-        add_bullet(date, "Task A", None, &vec![], &vec![])?;
-        add_bullet(date, "Task B", Some(2), &vec!["important".to_string()], &vec!["Note 1".to_string(), "Note 2".to_string()])?;
-        add_bullet(date, "Task C", None, &vec![], &vec![])?;
-        
-        // Verify initial state
-        let path = file_for(date)?;
-        let initial = parse_bullets(&read_file_lines(&path)?);
-        assert_eq!(initial.len(), 3, "Expected three bullets initially");
-        
-        // Find Task B's ID and delete it
-        let b_id = initial.iter()
-            .find(|b| b.text == "Task B")
-            .map(|b| b.visible_index)
-            .expect("Should find Task B");
-        delete_bullet(date, b_id)?;
-        
-        // Verify deletion
-        let after = parse_bullets(&read_file_lines(&path)?);
-        assert_eq!(after.len(), 2, "Should have two bullets after deletion");
-        
-        // Verify Task B is gone and others remain
-        assert!(after.iter().any(|b| b.text == "Task A"), "Task A should remain");
-        assert!(after.iter().any(|b| b.text == "Task C"), "Task C should remain");
-        assert!(!after.iter().any(|b| b.text == "Task B"), "Task B should be deleted");
-        
-        // Verify visible indices are renumbered correctly
-        assert_eq!(after[0].visible_index, 1, "First bullet should be index 1");
-        assert_eq!(after[1].visible_index, 2, "Second bullet should be index 2");
-        
-        Ok(())
-    }
-
-    #[test]
-    #[serial]  // Prevent parallel test runs
-    fn test_delete_meeting() -> Result<()> {
-        let _env = TestEnv::new();
-        let date = NaiveDate::from_ymd_opt(2025, 11, 6).unwrap();
-        
-        // Add a regular bullet and a meeting
-        add_bullet(date, "Regular Task", None, &vec![], &vec![])?;
-        add_meeting(date, 
-            NaiveTime::from_hms_opt(14, 30, 0).unwrap(),
-            45,
-            "Team Sync",
-            &vec!["work".to_string()],
-            &vec!["Prep agenda".to_string()]
-        )?;
-        
-        // Verify initial state
-        let path = file_for(date)?;
-        let initial = parse_bullets(&read_file_lines(&path)?);
-        assert_eq!(initial.len(), 2, "Expected two bullets initially");
-        
-        // Find and delete the meeting
-        let mtg_id = initial.iter()
-            .find(|b| b.text == "Team Sync")
-            .map(|b| b.visible_index)
-            .expect("Should find meeting");
-        delete_bullet(date, mtg_id)?;
-        
-        // Verify deletion
-        let after = parse_bullets(&read_file_lines(&path)?);
-        assert_eq!(after.len(), 1, "Should have one bullet after deletion");
-        assert_eq!(after[0].text, "Regular Task", "Regular task should remain");
-        assert!(!after.iter().any(|b| b.text == "Team Sync"), "Meeting should be deleted");
-        
-        Ok(())
-    }
-
-    #[test]
-    #[serial]  // Prevent parallel test runs
-    fn test_migrate_to_specific_date() -> Result<()> {
-        let _env = TestEnv::new();
-        let from = NaiveDate::from_ymd_opt(2025, 11, 4).unwrap();
-        let to = NaiveDate::from_ymd_opt(2025, 11, 10).unwrap();
-        
-        // Create a bullet on source date
-        add_bullet(from, "Task for next week", Some(2), &vec!["work".to_string()], &vec![])?;
-        
-        // Get the bullet ID
-        let from_path = file_for(from)?;
-        let initial = parse_bullets(&read_file_lines(&from_path)?);
-        assert_eq!(initial.len(), 1, "Should have one bullet");
-        let bullet_id = initial[0].visible_index;
-        
-        // Migrate to specific date
-        migrate_one(from, to, bullet_id)?;
-        
-        // Verify source is empty
-        let source_after = parse_bullets(&read_file_lines(&from_path)?);
-        assert_eq!(source_after.len(), 0, "Source should be empty after migration");
-        
-        // Verify target has the bullet
-        let to_path = file_for(to)?;
-        let target = parse_bullets(&read_file_lines(&to_path)?);
-        assert_eq!(target.len(), 1, "Target should have one bullet");
-        assert_eq!(target[0].text, "Task for next week", "Bullet text should match");
-        assert_eq!(target[0].priority, Some(2), "Priority should be preserved");
-        assert_eq!(target[0].tags, vec!["work"], "Tags should be preserved");
-        
-        Ok(())
-    }
-
-    #[test]
-    #[serial]  // Prevent parallel test runs
-    fn test_migrate_open_to_specific_date() -> Result<()> {
-        let _env = TestEnv::new();
-        let from = NaiveDate::from_ymd_opt(2025, 11, 3).unwrap();
-        let to = NaiveDate::from_ymd_opt(2025, 11, 15).unwrap();
-        
-        // Add bullets with different states
-        add_bullet(from, "Done Task", None, &vec![], &vec![])?;
-        add_bullet(from, "Open Task 1", Some(1), &vec!["tag1".to_string()], &vec![])?;
-        add_bullet(from, "Open Task 2", Some(2), &vec!["tag2".to_string()], &vec![])?;
-        
-        // Mark first task done
-        let from_path = file_for(from)?;
-        let initial = parse_bullets(&read_file_lines(&from_path)?);
-        let done_id = initial.iter()
-            .find(|b| b.text == "Done Task")
-            .map(|b| b.visible_index)
-            .expect("Should find done task");
-        mark_done(from, done_id)?;
-        
-        // Migrate all open tasks to specific date
-        migrate_open(from, to)?;
-        
-        // Verify source only has completed task
-        let source_after = parse_bullets(&read_file_lines(&from_path)?);
-        assert_eq!(source_after.len(), 1, "Source should have one bullet");
-        assert!(source_after[0].completed, "Remaining bullet should be completed");
-        
-        // Verify target has both open tasks
-        let to_path = file_for(to)?;
-        let target = parse_bullets(&read_file_lines(&to_path)?);
-        assert_eq!(target.len(), 2, "Target should have two bullets");
-        assert!(target.iter().any(|b| b.text == "Open Task 1"), "Open Task 1 should be migrated");
-        assert!(target.iter().any(|b| b.text == "Open Task 2"), "Open Task 2 should be migrated");
-        
-        Ok(())
-    }
-
-
-}
-
@@ -0,0 +1,134 @@
+//! Month collections. `YYYY-MM.md` beside the day files is the monthly
+//! log of that month; the collections of months still ahead make up the
+//! future log. Once a month begins, its open items are migrated into the
+//! daily log like any other bullet, leaving `[>]` markers behind.
+
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use chrono::NaiveDate;
+
+use crate::bullet::{parse_bullets, Bullet, BulletRef, Kind, Link, State};
+use crate::date::month_of;
+use crate::journal::{block_end, read_file_lines, Journal};
+
+impl Journal {
+	/// Collection file of the month containing `month`.
+	pub fn file_for_month(&self, month: NaiveDate) -> PathBuf {
+		self.root().join(format!("{}.md", month.format("%Y-%m")))
+	}
+
+	/// First day of every month that has a collection, oldest first.
+	pub fn months(&self) -> Result<Vec<NaiveDate>> {
+		let mut months = Vec::new();
+		if !self.root().exists() { return Ok(months); }
+		for entry in std::fs::read_dir(self.root()).with_context(|| format!("read {}", self.root().display()))? {
+			let name = entry?.file_name();
+			let Some(stem) = name.to_str().and_then(|n| n.strip_suffix(".md")) else { continue };
+			if stem.len() != 7 { continue; }
+			if let Ok(d) = NaiveDate::parse_from_str(&format!("{}-01", stem), "%Y-%m-%d") { months.push(d); }
+		}
+		months.sort();
+		Ok(months)
+	}
+
+	/// Bullets in the collection of `month`, in file order.
+	pub fn month_bullets(&self, month: NaiveDate) -> Result<Vec<Bullet>> {
+		Ok(parse_bullets(&read_file_lines(&self.file_for_month(month))?))
+	}
+
+	/// Collections of `from`'s month and every later one that has entries.
+	pub fn future_log(&self, from: NaiveDate) -> Result<Vec<(NaiveDate, Vec<Bullet>)>> {
+		let mut log = Vec::new();
+		for month in self.months()?.into_iter().filter(|m| *m >= month_of(from)) {
+			let bullets = self.month_bullets(month)?;
+			if !bullets.is_empty() { log.push((month, bullets)); }
+		}
+		Ok(log)
+	}
+
+	/// Append a task, event or note to the collection of `month` under a
+	/// fresh ID and return it.
+	pub fn add_to_month(&self, month: NaiveDate, kind: Kind, text: &str, priority: Option<u8>, tags: &[String], notes: &[String]) -> Result<Bullet> {
+		self.add_to(&self.file_for_month(month), kind, text, priority, tags, notes)
+	}
+
+	/// Move open tasks of `month` into the daily log of `to`, which must not
+	/// be before the month begins: those picked by `refs`, or all of them
+	/// when `refs` is empty. Returns the moved bullets.
+	pub fn migrate_month(&self, month: NaiveDate, to: NaiveDate, refs: &[BulletRef]) -> Result<Vec<Bullet>> {
+		let month = month_of(month);
+		if to < month { bail!("{} has not begun on {}", month.format("%Y-%m"), to); }
+		self.operation("migrate", || {
+			let path = self.file_for_month(month);
+			let mut lines = read_file_lines(&path)?;
+			let bullets = parse_bullets(&lines);
+			for r in refs {
+				let Some(b) = bullets.iter().find(|b| r.matches(b)) else { bail!("bullet {} not found in {}", r, month.format("%Y-%m")) };
+				if !b.is_open() { bail!("bullet {} is not an open task", r); }
+			}
+			let mut covered = 0;
+			let mut picked = Vec::new();
+			for b in bullets {
+				if b.line_index < covered || !b.is_open() { continue; }
+				if !refs.is_empty() && !refs.iter().any(|r| r.matches(&b)) { continue; }
+				covered = block_end(&lines, b.line_index);
+				picked.push(b);
+			}
+			let mut removed = 0;
+			for b in &picked {
+				let at = Bullet { line_index: b.line_index - removed, ..b.clone() };
+				removed += self.forward(Link::Month(month), &mut lines, &at, &self.file_for(to), Link::Day(to), State::Migrated)?;
+			}
+			if !picked.is_empty() { self.write_lines(&path, &lines)?; }
+			Ok(picked)
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::Page;
+    use crate::test_support::TestEnv;
+
+    fn d(y: i32, m: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, day).unwrap()
+    }
+
+    #[test]
+    fn test_future_log_migrates_into_days() -> Result<()> {
+        let env = TestEnv::new();
+        let j = &env.journal;
+        let march = d(2026, 3, 1);
+        let passport = j.add_to_month(march, Kind::Task, "Renew passport", Some(3), &["admin".into()], &["bring photos".into()])?;
+        j.add_to_month(march, Kind::Event, "Conference", None, &[], &[])?;
+        j.add_to_month(d(2026, 4, 15), Kind::Task, "File taxes", None, &[], &[])?;
+        j.add_bullet(d(2026, 3, 2), "Day task", None, &[], &[])?;
+
+        assert_eq!(j.file_for_month(march).file_name().unwrap(), "2026-03.md");
+        assert_eq!(j.months()?, vec![march, d(2026, 4, 1)]);
+        assert_eq!(j.days()?, vec![d(2026, 3, 2)], "Month files are not days");
+        let log = j.future_log(d(2026, 3, 20))?;
+        assert_eq!(log.iter().map(|(m, b)| (*m, b.len())).collect::<Vec<_>>(), vec![(march, 2), (d(2026, 4, 1), 1)]);
+        assert!(j.future_log(d(2026, 5, 1))?.is_empty());
+
+        assert!(j.migrate_month(march, d(2026, 2, 27), &[]).is_err(), "The month has not begun yet");
+        let moved = j.migrate_month(march, d(2026, 3, 2), &[])?;
+        assert_eq!(moved.len(), 1, "Only open tasks move");
+
+        let day = j.bullets(d(2026, 3, 2))?;
+        assert_eq!(day[1].text, "Renew passport");
+        assert_eq!((day[1].id.clone(), day[1].priority, day[1].notes.clone()), (passport.id.clone(), Some(3), vec!["bring photos".to_string()]));
+        assert_eq!((day[1].migrated_from, day[1].migrated_from_month), (None, Some(march)), "The day points back at the month");
+        j.migrate_one(d(2026, 3, 2), d(2026, 3, 5), BulletRef::Id(passport.id.clone().unwrap()))?;
+        let hops: Vec<_> = j.history(passport.id.as_deref().unwrap())?.into_iter().map(|h| (h.page, h.date)).collect();
+        assert_eq!(hops, vec![(Page::Month, march), (Page::Day, d(2026, 3, 2)), (Page::Day, d(2026, 3, 5))], "History starts in the future log");
+        let left = j.month_bullets(march)?;
+        assert_eq!((left[0].state, left[0].migrated_to), (State::Migrated, Some(d(2026, 3, 2))));
+        assert!(left[0].notes.is_empty(), "Notes move with the bullet");
+        assert!(j.migrate_month(march, d(2026, 3, 3), &[])?.is_empty(), "Nothing left to migrate");
+        assert!(j.migrate_month(march, d(2026, 3, 3), &[BulletRef::Id(passport.id.unwrap())]).is_err());
+        Ok(())
+    }
+}
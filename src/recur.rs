@@ -0,0 +1,516 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use chrono::{Datelike, NaiveDate, NaiveTime, Weekday};
+use chrono_tz::Tz;
+
+use crate::bullet::{check_text, format_bullet_line, meeting_prefix, new_id, occurrence_id, parse_bullets, Bullet, BulletRef, Kind};
+use crate::journal::{read_file_lines, Journal};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Freq {
+	Daily,
+	Weekly,
+	Monthly,
+}
+
+/// A recurrence rule: the RFC 5545 RRULE subset `FREQ`, `INTERVAL`, `BYDAY`,
+/// `BYMONTHDAY`, `COUNT` and `UNTIL`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule {
+	pub freq: Freq,
+	pub interval: u32,
+	pub by_day: Vec<Weekday>,
+	pub by_month_day: Vec<i32>, // negative counts back from the end of the month
+	pub count: Option<u32>,
+	pub until: Option<NaiveDate>,
+}
+
+impl Rule {
+	fn new(freq: Freq) -> Self {
+		Rule { freq, interval: 1, by_day: Vec::new(), by_month_day: Vec::new(), count: None, until: None }
+	}
+
+	/// Parse a shorthand (`daily`, `weekdays`, `weekly`, `weekly:mon,thu`,
+	/// `monthly`, `monthly:15`) or an RRULE such as `FREQ=WEEKLY;BYDAY=MO,TH`.
+	pub fn parse(s: &str) -> Result<Self> {
+		let lower = s.trim().to_lowercase();
+		match lower.as_str() {
+			"daily" => return Ok(Rule::new(Freq::Daily)),
+			"weekly" => return Ok(Rule::new(Freq::Weekly)),
+			"monthly" => return Ok(Rule::new(Freq::Monthly)),
+			"weekdays" => {
+				let mut r = Rule::new(Freq::Weekly);
+				r.by_day = vec![Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri];
+				return Ok(r);
+			}
+			_ => {}
+		}
+		if let Some(days) = lower.strip_prefix("weekly:") {
+			let mut r = Rule::new(Freq::Weekly);
+			r.by_day = parse_list(days, |d| parse_weekday(d).with_context(|| format!("invalid weekday: {}", d)))?;
+			return Ok(r);
+		}
+		if let Some(days) = lower.strip_prefix("monthly:") {
+			let mut r = Rule::new(Freq::Monthly);
+			r.by_month_day = parse_list(days, parse_month_day)?;
+			return Ok(r);
+		}
+		Self::parse_rrule(s)
+	}
+
+	fn parse_rrule(s: &str) -> Result<Self> {
+		let body = s.trim();
+		let body = body.strip_prefix("RRULE:").or_else(|| body.strip_prefix("rrule:")).unwrap_or(body);
+		let mut freq = None;
+		let mut r = Rule::new(Freq::Daily);
+		for part in body.split(';').filter(|p| !p.is_empty()) {
+			let Some((key, value)) = part.split_once('=') else { bail!("invalid recurrence: {}", s) };
+			match key.to_uppercase().as_str() {
+				"FREQ" => freq = Some(match value.to_uppercase().as_str() {
+					"DAILY" => Freq::Daily,
+					"WEEKLY" => Freq::Weekly,
+					"MONTHLY" => Freq::Monthly,
+					_ => bail!("unsupported FREQ: {}", value),
+				}),
+				"INTERVAL" => {
+					r.interval = value.parse().with_context(|| format!("invalid INTERVAL: {}", value))?;
+					if r.interval == 0 { bail!("INTERVAL must be at least 1"); }
+				}
+				"BYDAY" => r.by_day = parse_list(value, |d| parse_weekday(d).with_context(|| format!("invalid BYDAY: {}", d)))?,
+				"BYMONTHDAY" => r.by_month_day = parse_list(value, parse_month_day)?,
+				"COUNT" => r.count = Some(value.parse().with_context(|| format!("invalid COUNT: {}", value))?),
+				"UNTIL" => {
+					let date = value.get(..8).unwrap_or(value);
+					r.until = Some(NaiveDate::parse_from_str(date, "%Y%m%d").with_context(|| format!("invalid UNTIL: {}", value))?);
+				}
+				"WKST" => {}
+				_ => bail!("unsupported recurrence part: {}", key),
+			}
+		}
+		let Some(freq) = freq else { bail!("invalid recurrence: {} (expected daily, weekdays, weekly:mon,thu, monthly:15 or an RRULE)", s) };
+		r.freq = freq;
+		Ok(r)
+	}
+
+	/// Whether the pattern (ignoring `COUNT`) hits `date` for a series starting on `start`.
+	fn matches_pattern(&self, start: NaiveDate, date: NaiveDate) -> bool {
+		if date < start { return false; }
+		if let Some(u) = self.until { if date > u { return false; } }
+		let interval = self.interval.max(1) as i64;
+		match self.freq {
+			Freq::Daily => {
+				(date - start).num_days() % interval == 0
+					&& (self.by_day.is_empty() || self.by_day.contains(&date.weekday()))
+			}
+			Freq::Weekly => {
+				let week_start = |d: NaiveDate| d - chrono::Days::new(d.weekday().num_days_from_monday() as u64);
+				let weeks = (week_start(date) - week_start(start)).num_days() / 7;
+				let on_day = if self.by_day.is_empty() { date.weekday() == start.weekday() } else { self.by_day.contains(&date.weekday()) };
+				weeks % interval == 0 && on_day
+			}
+			Freq::Monthly => {
+				let months = (date.year() - start.year()) as i64 * 12 + date.month() as i64 - start.month() as i64;
+				let day = date.day() as i32;
+				let on_day = if self.by_month_day.is_empty() {
+					day == start.day() as i32
+				} else {
+					let last = days_in_month(date) as i32;
+					self.by_month_day.iter().any(|&d| if d > 0 { day == d } else { day == last + d + 1 })
+				};
+				months % interval == 0 && on_day
+			}
+		}
+	}
+
+	/// Whether a series starting on `start` has an occurrence on `date`.
+	pub fn occurs_on(&self, start: NaiveDate, date: NaiveDate) -> bool {
+		if !self.matches_pattern(start, date) { return false; }
+		let Some(count) = self.count else { return true };
+		let seen = start.iter_days().take_while(|d| *d <= date).filter(|d| self.matches_pattern(start, *d)).count();
+		seen <= count as usize
+	}
+}
+
+impl fmt::Display for Rule {
+	/// Canonical RRULE form, as stored in `recurring.md`.
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let freq = match self.freq {
+			Freq::Daily => "DAILY",
+			Freq::Weekly => "WEEKLY",
+			Freq::Monthly => "MONTHLY",
+		};
+		write!(f, "FREQ={}", freq)?;
+		if self.interval > 1 { write!(f, ";INTERVAL={}", self.interval)?; }
+		if !self.by_day.is_empty() {
+			let days: Vec<&str> = self.by_day.iter().map(|d| weekday_code(*d)).collect();
+			write!(f, ";BYDAY={}", days.join(","))?;
+		}
+		if !self.by_month_day.is_empty() {
+			let days: Vec<String> = self.by_month_day.iter().map(|d| d.to_string()).collect();
+			write!(f, ";BYMONTHDAY={}", days.join(","))?;
+		}
+		if let Some(c) = self.count { write!(f, ";COUNT={}", c)?; }
+		if let Some(u) = self.until { write!(f, ";UNTIL={}", u.format("%Y%m%d"))?; }
+		Ok(())
+	}
+}
+
+fn parse_list<T>(s: &str, f: impl Fn(&str) -> Result<T>) -> Result<Vec<T>> {
+	s.split(',').map(|p| p.trim()).filter(|p| !p.is_empty()).map(f).collect()
+}
+
+fn parse_month_day(s: &str) -> Result<i32> {
+	let d: i32 = s.parse().with_context(|| format!("invalid day of month: {}", s))?;
+	if d == 0 || !(-31..=31).contains(&d) { bail!("invalid day of month: {}", s); }
+	Ok(d)
+}
+
+/// Accepts RRULE codes (`MO`), short (`mon`) and full (`monday`) names.
+pub(crate) fn parse_weekday(s: &str) -> Option<Weekday> {
+	let s = s.trim().to_lowercase();
+	let day = match s.as_str() {
+		"mo" | "mon" | "monday" => Weekday::Mon,
+		"tu" | "tue" | "tues" | "tuesday" => Weekday::Tue,
+		"we" | "wed" | "wednesday" => Weekday::Wed,
+		"th" | "thu" | "thur" | "thurs" | "thursday" => Weekday::Thu,
+		"fr" | "fri" | "friday" => Weekday::Fri,
+		"sa" | "sat" | "saturday" => Weekday::Sat,
+		"su" | "sun" | "sunday" => Weekday::Sun,
+		_ => return None,
+	};
+	Some(day)
+}
+
+fn weekday_code(d: Weekday) -> &'static str {
+	match d {
+		Weekday::Mon => "MO",
+		Weekday::Tue => "TU",
+		Weekday::Wed => "WE",
+		Weekday::Thu => "TH",
+		Weekday::Fri => "FR",
+		Weekday::Sat => "SA",
+		Weekday::Sun => "SU",
+	}
+}
+
+pub(crate) fn days_in_month(date: NaiveDate) -> u32 {
+	let (y, m) = if date.month() == 12 { (date.year() + 1, 1) } else { (date.year(), date.month() + 1) };
+	let next = NaiveDate::from_ymd_opt(y, m, 1).expect("valid month");
+	(next - chrono::Days::new(1)).day()
+}
+
+/// A bullet or meeting stored once in `recurring.md` and shown on every day its rule hits.
+#[derive(Debug, Clone)]
+pub struct Recurring {
+	pub template: Bullet,
+	pub rule: Rule,
+	pub start: NaiveDate,
+	pub skips: Vec<NaiveDate>,
+}
+
+impl Recurring {
+	pub fn id(&self) -> &str {
+		self.template.id.as_deref().unwrap_or_default()
+	}
+
+	/// Whether an occurrence is due on `date` and has not been skipped.
+	pub fn is_due(&self, date: NaiveDate) -> bool {
+		self.rule.occurs_on(self.start, date) && !self.skips.contains(&date)
+	}
+
+	fn to_lines(&self) -> Vec<String> {
+		let t = &self.template;
+		let mut lines = vec![format_bullet_line(t.kind, &t.full_text(), t.priority, &t.tags, self.id())];
+		lines.extend(t.notes.iter().map(|n| format!("  - note: {}", n)));
+		lines.push(format!("  - every: {}", self.rule));
+		lines.push(format!("  - start: {}", self.start));
+		if !self.skips.is_empty() {
+			let skips: Vec<String> = self.skips.iter().map(|d| d.to_string()).collect();
+			lines.push(format!("  - skip: {}", skips.join(", ")));
+		}
+		lines
+	}
+}
+
+/// Parse `recurring.md`: each entry is a bullet line with its notes followed
+/// by `  - every:`, `  - start:` and optional `  - skip:` lines.
+fn parse_recurring(lines: &[String]) -> Result<Vec<Recurring>> {
+	let mut out = Vec::new();
+	for template in parse_bullets(lines) {
+		let mut rule = None;
+		let mut start = None;
+		let mut skips = Vec::new();
+		for line in lines.iter().skip(template.line_index + 1) {
+			let Some(meta) = line.strip_prefix("  - ") else { break };
+			if let Some(v) = meta.strip_prefix("every: ") {
+				rule = Some(Rule::parse(v)?);
+			} else if let Some(v) = meta.strip_prefix("start: ") {
+				start = Some(crate::bullet::parse_iso_date(v.trim())?);
+			} else if let Some(v) = meta.strip_prefix("skip: ") {
+				for d in v.split(',').map(|d| d.trim()).filter(|d| !d.is_empty()) {
+					skips.push(crate::bullet::parse_iso_date(d)?);
+				}
+			}
+		}
+		let (Some(rule), Some(start)) = (rule, start) else { bail!("recurring entry \"{}\" is missing every:/start:", template.text) };
+		if template.id.is_none() { bail!("recurring entry \"{}\" has no ^id", template.text); }
+		out.push(Recurring { template, rule, start, skips });
+	}
+	Ok(out)
+}
+
+impl Journal {
+	fn recurring_path(&self) -> PathBuf { self.root().join("recurring.md") }
+
+	/// Every recurring definition, in file order.
+	pub fn recurring(&self) -> Result<Vec<Recurring>> {
+		parse_recurring(&read_file_lines(&self.recurring_path())?)
+	}
+
+	fn write_recurring(&self, all: &[Recurring]) -> Result<()> {
+		let lines: Vec<String> = all.iter().flat_map(|r| r.to_lines()).collect();
+		self.write_lines(&self.recurring_path(), &lines)
+	}
+
+	/// Store a recurring bullet starting on `start`. `text` may carry a `[mtg HH:MM D]` prefix.
+	pub fn add_recurring(&self, start: NaiveDate, rule: Rule, text: &str, priority: Option<u8>, tags: &[String], notes: &[String]) -> Result<Recurring> {
+		check_text(text)?;
+		self.operation("recur", || {
+			let mut all = self.recurring()?;
+			let id = new_id(&self.recurring_ids()?);
+			let line = format_bullet_line(Kind::Task, text, priority, tags, &id);
+			let mut template = parse_bullets(&[line]).pop().expect("bullet just formatted");
+			template.notes = notes.to_vec();
+			let r = Recurring { template, rule, start, skips: Vec::new() };
+			all.push(r.clone());
+			self.write_recurring(&all)?;
+			Ok(r)
+		})
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	pub fn add_recurring_meeting(&self, start: NaiveDate, rule: Rule, time: NaiveTime, duration_min: u32, tz: Option<Tz>, title: &str, tags: &[String], notes: &[String]) -> Result<Recurring> {
+		let full = format!("{}{}", meeting_prefix(time, Some(duration_min), tz), title);
+		self.add_recurring(start, rule, &full, None, tags, notes)
+	}
+
+	/// Delete a recurring definition. Occurrences already written to day files are kept.
+	pub fn remove_recurring(&self, id: &str) -> Result<Recurring> {
+		self.operation("recur remove", || {
+			let mut all = self.recurring()?;
+			let Some(pos) = all.iter().position(|r| r.id() == id) else { bail!("no recurring entry ^{}", id) };
+			let removed = all.remove(pos);
+			self.write_recurring(&all)?;
+			Ok(removed)
+		})
+	}
+
+	pub(crate) fn recurring_ids(&self) -> Result<HashSet<String>> {
+		Ok(self.recurring()?.iter().map(|r| r.id().to_string()).collect())
+	}
+
+	/// Append this day's not-yet-written occurrences to `bullets`, numbered as
+	/// if they followed the file's own lines, and flag bullets that belong to a
+	/// series. Each occurrence has an ID of its own and names its series.
+	pub(crate) fn add_occurrences(&self, date: NaiveDate, lines: &[String], bullets: &mut Vec<Bullet>) -> Result<()> {
+		let all = self.recurring()?;
+		if all.is_empty() { return Ok(()); }
+		for b in bullets.iter_mut() {
+			b.series = series_of(b, &all);
+			b.recurring = b.series.is_some();
+		}
+		let mut next_line = lines.len();
+		for r in all.iter().filter(|r| r.is_due(date)) {
+			if bullets.iter().any(|b| b.series.as_deref() == Some(r.id())) { continue; }
+			let visible_index = bullets.len() + 1;
+			let id = Some(occurrence_id(r.id(), date));
+			bullets.push(Bullet { line_index: next_line, visible_index, id, recurring: true, series: Some(r.id().to_string()), ..r.template.clone() });
+			next_line += 1 + r.template.notes.len();
+		}
+		Ok(())
+	}
+
+	/// Write this day's virtual occurrences into its file so they can be edited
+	/// like any other bullet. Numbering is unchanged.
+	pub(crate) fn materialize(&self, date: NaiveDate) -> Result<()> {
+		let path = self.file_for(date);
+		let mut lines = read_file_lines(&path)?;
+		let file_bullets = parse_bullets(&lines);
+		let mut all = file_bullets.clone();
+		self.add_occurrences(date, &lines, &mut all)?;
+		if all.len() == file_bullets.len() { return Ok(()); }
+		for b in &all[file_bullets.len()..] { lines.extend(b.block()); }
+		self.write_lines(&path, &lines)
+	}
+
+	/// Record that the occurrence of a series on `date` is gone, so it is not
+	/// shown virtually again. No-op for bullets that are not part of a series.
+	pub(crate) fn suppress_occurrence(&self, date: NaiveDate, b: &Bullet) -> Result<()> {
+		let mut all = self.recurring()?;
+		let Some(id) = series_of(b, &all) else { return Ok(()) };
+		let Some(r) = all.iter_mut().find(|r| r.id() == id) else { return Ok(()) };
+		if !r.rule.occurs_on(r.start, date) || r.skips.contains(&date) { return Ok(()); }
+		r.skips.push(date);
+		r.skips.sort();
+		self.write_recurring(&all)
+	}
+
+	/// Skip one occurrence of a series on `date` without touching the others.
+	/// `id` may also be the ID of the series itself.
+	pub fn skip_occurrence(&self, date: NaiveDate, id: impl Into<BulletRef>) -> Result<Bullet> {
+		self.operation("skip", || {
+			let id = id.into();
+			let of_series = |b: &Bullet| matches!(&id, BulletRef::Id(s) if b.series.as_ref() == Some(s));
+			let Some(target) = self.bullets(date)?.into_iter().find(|b| id.matches(b) || of_series(b)) else { bail!("bullet {} not found", id) };
+			if !target.recurring { bail!("bullet {} is not part of a recurring series", id); }
+			let path = self.file_for(date);
+			let mut lines = read_file_lines(&path)?;
+			if target.line_index < lines.len() {
+				let end = crate::journal::block_end(&lines, target.line_index);
+				lines.drain(target.line_index..end);
+				self.write_lines(&path, &lines)?;
+			}
+			self.suppress_occurrence(date, &target)?;
+			Ok(target)
+		})
+	}
+}
+
+/// Series a day-file bullet belongs to: its `↻` marker, or its own ID for
+/// occurrences written out before they had IDs of their own.
+fn series_of(b: &Bullet, all: &[Recurring]) -> Option<String> {
+	b.series.clone().or_else(|| b.id.clone().filter(|id| all.iter().any(|r| r.id() == id)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TestEnv;
+
+    fn d(y: i32, m: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, day).unwrap()
+    }
+
+    #[test]
+    fn test_rule_shorthands() -> Result<()> {
+        let start = d(2025, 11, 3); // Monday
+        let weekdays = Rule::parse("weekdays")?;
+        assert!(weekdays.occurs_on(start, d(2025, 11, 7)), "Friday is a weekday");
+        assert!(!weekdays.occurs_on(start, d(2025, 11, 8)), "Saturday is not a weekday");
+
+        let mon_thu = Rule::parse("weekly:mon,thu")?;
+        assert!(mon_thu.occurs_on(start, d(2025, 11, 6)), "Thursday should match");
+        assert!(!mon_thu.occurs_on(start, d(2025, 11, 5)), "Wednesday should not match");
+        assert!(!mon_thu.occurs_on(start, d(2025, 11, 2)), "Nothing before the start date");
+
+        let monthly = Rule::parse("monthly:15")?;
+        assert!(monthly.occurs_on(start, d(2026, 2, 15)), "Every 15th should match");
+        assert!(!monthly.occurs_on(start, d(2026, 2, 16)), "Other days should not match");
+        Ok(())
+    }
+
+    #[test]
+    fn test_rrule_subset() -> Result<()> {
+        let start = d(2025, 11, 3);
+        let r = Rule::parse("FREQ=WEEKLY;INTERVAL=2;BYDAY=MO;COUNT=3")?;
+        assert_eq!(r.to_string(), "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO;COUNT=3", "RRULE should round-trip");
+        assert!(r.occurs_on(start, d(2025, 11, 17)), "Every other Monday");
+        assert!(!r.occurs_on(start, d(2025, 11, 10)), "Skipped week");
+        assert!(r.occurs_on(start, d(2025, 12, 1)), "Third occurrence");
+        assert!(!r.occurs_on(start, d(2025, 12, 15)), "COUNT exhausted");
+
+        let last = Rule::parse("RRULE:FREQ=MONTHLY;BYMONTHDAY=-1;UNTIL=20260301")?;
+        assert!(last.occurs_on(start, d(2026, 2, 28)), "Last day of February");
+        assert!(!last.occurs_on(start, d(2026, 3, 31)), "Past UNTIL");
+        assert!(Rule::parse("FREQ=YEARLY").is_err(), "Unsupported FREQ should be rejected");
+        Ok(())
+    }
+
+    #[test]
+    fn test_recurring_occurrences_are_independent() -> Result<()> {
+        let env = TestEnv::new();
+        let j = &env.journal;
+        let mon = d(2025, 11, 3);
+        let (tue, wed) = (d(2025, 11, 4), d(2025, 11, 5));
+        let r = j.add_recurring(mon, Rule::parse("weekdays")?, "[mtg 09:30 15] Standup", None, &["work".to_string()], &[])?;
+        j.add_bullet(tue, "One-off", None, &[], &[])?;
+
+        // Shown virtually after the day's own bullets, without writing files
+        let tue_bullets = j.bullets(tue)?;
+        assert_eq!(tue_bullets.len(), 2, "Occurrence should be listed alongside the day's bullets");
+        assert_eq!(tue_bullets[1].text, "Standup", "Occurrence text mismatch");
+        assert_eq!(tue_bullets[1].visible_index, 2, "Occurrence should be numbered after file bullets");
+        assert!(tue_bullets[1].recurring, "Occurrence should be flagged recurring");
+        assert!(!j.file_for(wed).exists(), "Virtual occurrences should not create day files");
+        assert!(j.bullets(d(2025, 11, 8))?.is_empty(), "No occurrence on Saturday");
+
+        // Completing Tuesday's occurrence writes it out and leaves Wednesday alone
+        j.mark_done(tue, 2)?;
+        assert!(j.bullets(tue)?[1].is_done(), "Tuesday's occurrence should be done");
+        assert!(!j.bullets(wed)?[0].is_done(), "Wednesday's occurrence should still be open");
+        assert_eq!(j.bullets(tue)?.len(), 2, "Materialized occurrence should not be duplicated");
+
+        // Skipping Wednesday hides only Wednesday
+        j.skip_occurrence(wed, BulletRef::Id(r.id().to_string()))?;
+        assert!(j.bullets(wed)?.is_empty(), "Skipped occurrence should be hidden");
+        assert_eq!(j.bullets(d(2025, 11, 6))?.len(), 1, "Thursday should be unaffected");
+
+        // Deleting a written-out occurrence does not bring the virtual one back
+        j.delete_bullet(tue, 2)?;
+        assert_eq!(j.bullets(tue)?.len(), 1, "Deleted occurrence should stay deleted");
+        Ok(())
+    }
+
+    #[test]
+    fn test_occurrences_have_their_own_ids() -> Result<()> {
+        let env = TestEnv::new();
+        let j = &env.journal;
+        let (mon, tue, wed) = (d(2025, 11, 3), d(2025, 11, 4), d(2025, 11, 5));
+        let r = j.add_recurring(mon, Rule::parse("daily")?, "Stretch", None, &[], &[])?;
+        let shown = j.bullets(mon)?[0].clone();
+        assert_ne!(shown.id.as_deref(), Some(r.id()), "Occurrences don't reuse the series ID");
+        assert_ne!(shown.id, j.bullets(tue)?[0].id, "Each day's occurrence has its own ID");
+
+        // Writing it out keeps the ID it was shown with and names the series.
+        let mon_id = shown.id.clone().unwrap();
+        j.mark_done(mon, BulletRef::Id(mon_id.clone()))?;
+        let line = std::fs::read_to_string(j.file_for(mon))?;
+        assert_eq!(line, format!("- [x] Stretch ↻ {} ^{}\n", r.id(), mon_id));
+        assert_eq!(j.bullets(mon)?.len(), 1, "The written occurrence replaces the virtual one");
+
+        // Migrating one occurrence onto another day leaves that day's own occurrence alone.
+        let tue_id = j.bullets(tue)?[0].id.clone().unwrap();
+        j.migrate_one(tue, wed, BulletRef::Id(tue_id.clone()))?;
+        let wed_bullets = j.bullets(wed)?;
+        assert_eq!(wed_bullets.len(), 2, "Wednesday shows its occurrence and the migrated one");
+        assert!(wed_bullets[0].series.is_none() && wed_bullets[1].recurring, "Only the day's own occurrence is part of the series");
+        let hops: Vec<NaiveDate> = j.history(&tue_id)?.into_iter().map(|h| h.date).collect();
+        assert_eq!(hops, vec![tue, wed], "History follows one occurrence, not the series");
+        Ok(())
+    }
+
+    #[test]
+    fn test_skip_is_one_undo_step() -> Result<()> {
+        let env = TestEnv::new();
+        let j = &env.journal;
+        let (mon, tue) = (d(2025, 11, 3), d(2025, 11, 4));
+        let r = j.add_recurring(mon, Rule::parse("daily")?, "Stretch", None, &[], &[])?;
+        j.mark_done(mon, 1)?;
+        let day = std::fs::read_to_string(j.file_for(mon))?;
+        j.add_bullet(tue, "Other", None, &[], &[])?;
+        j.mark_done(tue, 2)?;
+
+        // A written-out occurrence: the skip touches the day file and recurring.md.
+        j.skip_occurrence(tue, BulletRef::Id(r.id().to_string()))?;
+        assert_eq!(j.bullets(tue)?.len(), 1);
+        let undone = j.undo()?.expect("something to undo");
+        assert_eq!((undone.label.as_str(), undone.files.len()), ("skip", 2), "Both files belong to the skip");
+        assert!(j.bullets(tue)?[1].is_done(), "The occurrence is back as it was");
+        assert!(j.recurring()?[0].skips.is_empty(), "The skip is forgotten");
+        assert_eq!(std::fs::read_to_string(j.file_for(mon))?, day, "Other days are untouched");
+        Ok(())
+    }
+}
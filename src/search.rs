@@ -0,0 +1,330 @@
+use anyhow::{bail, Context, Result};
+use chrono::NaiveDate;
+use regex::{Regex, RegexBuilder};
+
+use crate::bullet::{parse_priority_opt, Bullet, BulletRef, Kind, Link, State};
+use crate::clock::Clock;
+use crate::date::{parse_date_at, DateRange};
+use crate::journal::Journal;
+
+/// One piece of free text in a query.
+#[derive(Debug, Clone)]
+enum TextTerm {
+	/// Case-insensitive substring, stored lowercased.
+	Substring(String),
+	Regex(Regex),
+}
+
+/// A parsed `bj search` query.
+///
+/// Words are matched as case-insensitive substrings against the bullet text
+/// and notes; `/pattern/` is a regular expression. Structured filters:
+/// `tag:NAME`, `priority:low|med|high`,
+/// `status:open|done|migrated|scheduled|cancelled`, `is:meeting|task|event|note`,
+/// `before:YYYY-MM-DD` and `after:YYYY-MM-DD` (both exclusive; a month page
+/// counts as its first day, and collections are left out). All terms must match.
+#[derive(Debug, Clone, Default)]
+pub struct Query {
+	text: Vec<TextTerm>,
+	tags: Vec<String>,
+	priority: Option<u8>,
+	state: Option<State>,
+	kind: Option<Kind>,
+	meeting: bool,
+	before: Option<NaiveDate>,
+	after: Option<NaiveDate>,
+}
+
+/// A bullet found by [`Journal::search`].
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+	pub date: NaiveDate,
+	pub page: Page,
+	pub bullet: Bullet,
+}
+
+/// The file a [`SearchHit`] was found in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Page {
+	/// The day file of `date`.
+	Day,
+	/// The month page of the month starting on `date`.
+	Month,
+	/// A named collection; `date` is the day the bullet moved from or to.
+	Collection(String),
+}
+
+impl SearchHit {
+	/// Whether this hit sits on the page `link` points at.
+	fn is_on(&self, link: &Link) -> bool {
+		match link {
+			Link::Day(d) => self.page == Page::Day && self.date == *d,
+			Link::Month(m) => self.page == Page::Month && self.date == *m,
+			Link::Collection(name) => self.page == Page::Collection(name.clone()),
+		}
+	}
+}
+
+impl Query {
+	pub fn parse(s: &str) -> Result<Self> {
+		Self::parse_at(s, Clock::system().today())
+	}
+
+	/// Parse with `before:` and `after:` dates relative to `today`.
+	pub fn parse_at(s: &str, today: NaiveDate) -> Result<Self> {
+		let mut q = Query::default();
+		for term in s.split_whitespace() {
+			if let Some(t) = term.strip_prefix("tag:") {
+				q.tags.push(t.trim_start_matches('#').to_string());
+			} else if let Some(p) = term.strip_prefix("priority:") {
+				q.priority = parse_priority_opt(Some(p))?;
+			} else if let Some(st) = term.strip_prefix("status:") {
+				q.state = Some(match st {
+					"open" => State::Open,
+					"done" => State::Done,
+					"migrated" => State::Migrated,
+					"scheduled" => State::Scheduled,
+					"cancelled" | "canceled" => State::Cancelled,
+					_ => bail!("invalid status: {} (expected open, done, migrated, scheduled or cancelled)", st),
+				});
+			} else if let Some(k) = term.strip_prefix("is:") {
+				match k {
+					"meeting" => q.meeting = true,
+					"task" => q.kind = Some(Kind::Task),
+					"event" => q.kind = Some(Kind::Event),
+					"note" => q.kind = Some(Kind::Note),
+					_ => bail!("unknown filter: is:{}", k),
+				}
+			} else if let Some(d) = term.strip_prefix("before:") {
+				q.before = Some(parse_date_at(d, today)?);
+			} else if let Some(d) = term.strip_prefix("after:") {
+				q.after = Some(parse_date_at(d, today)?);
+			} else if let Some(pat) = term.strip_prefix('/').and_then(|t| t.strip_suffix('/')).filter(|p| !p.is_empty()) {
+				let re = RegexBuilder::new(pat).case_insensitive(true).build().with_context(|| format!("invalid regex: {}", pat))?;
+				q.text.push(TextTerm::Regex(re));
+			} else {
+				q.text.push(TextTerm::Substring(term.to_lowercase()));
+			}
+		}
+		Ok(q)
+	}
+
+	/// Whether a day file can contain matches at all.
+	pub fn includes_date(&self, date: NaiveDate) -> bool {
+		if let Some(b) = self.before { if date >= b { return false; } }
+		if let Some(a) = self.after { if date <= a { return false; } }
+		true
+	}
+
+	pub fn matches(&self, date: NaiveDate, b: &Bullet) -> bool {
+		if !self.includes_date(date) { return false; }
+		if !b.matches(&self.tags, self.priority) { return false; }
+		if let Some(st) = self.state { if b.state != st { return false; } }
+		if let Some(k) = self.kind { if b.kind != k { return false; } }
+		if self.meeting && b.meeting_time.is_none() { return false; }
+		let haystacks: Vec<&str> = std::iter::once(b.text.as_str()).chain(b.notes.iter().map(|n| n.as_str())).collect();
+		self.text.iter().all(|term| match term {
+			TextTerm::Substring(s) => haystacks.iter().any(|h| h.to_lowercase().contains(s)),
+			TextTerm::Regex(re) => haystacks.iter().any(|h| re.is_match(h)),
+		})
+	}
+}
+
+impl Journal {
+	/// Every bullet in the journal matching `query`: day files oldest first,
+	/// then month pages, then collections.
+	pub fn search(&self, query: &Query) -> Result<Vec<SearchHit>> {
+		let mut hits = Vec::new();
+		for date in self.days()? {
+			if !query.includes_date(date) { continue; }
+			for bullet in self.bullets(date)? {
+				if query.matches(date, &bullet) {
+					hits.push(SearchHit { date, page: Page::Day, bullet });
+				}
+			}
+		}
+		for month in self.months()? {
+			if !query.includes_date(month) { continue; }
+			for bullet in self.month_bullets(month)? {
+				if query.matches(month, &bullet) {
+					hits.push(SearchHit { date: month, page: Page::Month, bullet });
+				}
+			}
+		}
+		// Collections are undated, so a date window leaves them out.
+		if query.before.is_some() || query.after.is_some() { return Ok(hits); }
+		for name in self.collections()? {
+			for bullet in self.collection_bullets(&name)? {
+				let date = self.collection_date(&bullet);
+				if query.matches(date, &bullet) {
+					hits.push(SearchHit { date, page: Page::Collection(name.clone()), bullet });
+				}
+			}
+		}
+		Ok(hits)
+	}
+
+	/// Bullets in `range` picked by any of `refs` or by `query`, in day
+	/// order. Across several days a `^id` picks the bullet where it lives
+	/// now, not the copies it was migrated from; positions only work on a
+	/// single day.
+	pub fn select(&self, range: DateRange, refs: &[BulletRef], query: Option<&Query>) -> Result<Vec<SearchHit>> {
+		if !range.is_single_day() && refs.iter().any(|r| matches!(r, BulletRef::Index(_))) {
+			bail!("bullet positions only work on a single day; use ^ids with a range");
+		}
+		let mut hits = Vec::new();
+		let mut found = vec![false; refs.len()];
+		for date in range.days() {
+			for bullet in self.bullets(date)? {
+				let forwarded = !range.is_single_day() && matches!(bullet.state, State::Migrated | State::Scheduled);
+				let mut picked = query.is_some_and(|q| q.matches(date, &bullet));
+				for (r, seen) in refs.iter().zip(found.iter_mut()) {
+					if r.matches(&bullet) && !(forwarded && matches!(r, BulletRef::Id(_))) { picked = true; *seen = true; }
+				}
+				if picked { hits.push(SearchHit { date, page: Page::Day, bullet }); }
+			}
+		}
+		if let Some((r, _)) = refs.iter().zip(&found).find(|(_, seen)| !**seen) {
+			bail!("bullet {} not found in {}", r, range);
+		}
+		Ok(hits)
+	}
+
+	/// Every appearance of bullet `id`, following its migration chain from the
+	/// first page it was logged on, through month pages and collections, to
+	/// where it lives now.
+	pub fn history(&self, id: &str) -> Result<Vec<SearchHit>> {
+		let mut hits: Vec<SearchHit> = Vec::new();
+		for date in self.days()? {
+			for bullet in self.bullets(date)? {
+				if bullet.id.as_deref() == Some(id) {
+					hits.push(SearchHit { date, page: Page::Day, bullet });
+				}
+			}
+		}
+		for month in self.months()? {
+			for bullet in self.month_bullets(month)? {
+				if bullet.id.as_deref() == Some(id) {
+					hits.push(SearchHit { date: month, page: Page::Month, bullet });
+				}
+			}
+		}
+		for name in self.collections()? {
+			for bullet in self.collection_bullets(&name)? {
+				if bullet.id.as_deref() == Some(id) {
+					let date = self.collection_date(&bullet);
+					hits.push(SearchHit { date, page: Page::Collection(name.clone()), bullet });
+				}
+			}
+		}
+		// Start where nothing points back to a known hop, then follow the arrows.
+		let known = |link: Option<Link>| link.is_some_and(|l| hits.iter().any(|h| h.is_on(&l)));
+		let mut chain = Vec::new();
+		let mut next = hits.iter().position(|h| !known(h.bullet.came_from()));
+		while let Some(i) = next {
+			let hop = hits.remove(i);
+			next = hop.bullet.went_to().and_then(|to| hits.iter().position(|h| h.is_on(&to)));
+			chain.push(hop);
+		}
+		// Anything not linked (manual edits, recurring occurrences) follows in date order.
+		chain.extend(hits);
+		Ok(chain)
+	}
+
+	/// The day a collection bullet is shown under: where it came from or
+	/// went to, else today.
+	fn collection_date(&self, b: &Bullet) -> NaiveDate {
+		b.migrated_from.or(b.migrated_to).unwrap_or_else(|| self.clock().today())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bullet::{parse_bullets, BulletRef};
+    use crate::test_support::TestEnv;
+
+    fn bullet(line: &str) -> Bullet {
+        parse_bullets(&[line.to_string(), "  - note: bring slides".to_string()]).remove(0)
+    }
+
+    #[test]
+    fn test_query_text_and_filters() -> Result<()> {
+        let d = NaiveDate::from_ymd_opt(2025, 11, 6).unwrap();
+        let b = bullet("- [ ] [mtg 10:00 30] (!!) Quarterly Review #work ^k3x9");
+
+        assert!(Query::parse("review")?.matches(d, &b), "Substring should be case-insensitive");
+        assert!(Query::parse("slides")?.matches(d, &b), "Notes should be searched");
+        assert!(Query::parse("/^quarter.*w$/")?.matches(d, &b), "Regex should match text");
+        assert!(Query::parse("tag:work priority:med status:open is:meeting")?.matches(d, &b), "Structured filters should match");
+        assert!(!Query::parse("status:done")?.matches(d, &b), "Status filter should exclude open bullets");
+        assert!(Query::parse("is:task")?.matches(d, &b) && !Query::parse("is:note")?.matches(d, &b), "Kind filter mismatch");
+        assert!(!Query::parse("review tag:home")?.matches(d, &b), "All terms must match");
+        assert!(!Query::parse("before:2025-11-06")?.matches(d, &b), "before: is exclusive");
+        assert!(Query::parse("after:2025-11-05 before:2025-11-07")?.matches(d, &b), "Date window should include the day");
+        assert!(Query::parse("status:maybe").is_err(), "Unknown status should be rejected");
+        Ok(())
+    }
+
+    #[test]
+    fn test_select_by_ids_and_query() -> Result<()> {
+        let env = TestEnv::new();
+        let j = &env.journal;
+        let (d1, d2) = (NaiveDate::from_ymd_opt(2025, 11, 3).unwrap(), NaiveDate::from_ymd_opt(2025, 11, 4).unwrap());
+        let moved = j.add_bullet(d1, "Moved along", None, &[], &[])?.id.unwrap();
+        j.add_bullet(d1, "Report", None, &["work".into()], &[])?;
+        let kept = j.add_bullet(d2, "Groceries", None, &[], &[])?.id.unwrap();
+        j.add_bullet(d2, "Standup notes", None, &["work".into()], &[])?;
+        j.migrate_one(d1, d2, BulletRef::Id(moved.clone()))?;
+        let week = DateRange::new(d1, d2)?;
+
+        let hits = j.select(week, &[BulletRef::Id(moved.clone()), BulletRef::Id(kept)], None)?;
+        assert_eq!(hits.iter().map(|h| (h.date, h.bullet.text.as_str())).collect::<Vec<_>>(), [(d2, "Groceries"), (d2, "Moved along")],
+            "An ID picks the live copy, not the migrated one");
+
+        let work = Query::parse("tag:work")?;
+        assert_eq!(j.select(week, &[], Some(&work))?.len(), 2, "The query applies to every day");
+        assert_eq!(j.select(DateRange::day(d1), &[BulletRef::Index(1)], None)?[0].bullet.id.as_deref(), Some(moved.as_str()), "Positions work on one day");
+        assert!(j.select(week, &[BulletRef::Index(1)], None).is_err(), "Positions are ambiguous over a range");
+        assert!(j.select(week, &[BulletRef::Id("nope".into())], None).is_err(), "Unknown IDs are reported");
+        Ok(())
+    }
+
+    #[test]
+    fn test_history_follows_migrations() -> Result<()> {
+        let env = TestEnv::new();
+        let j = &env.journal;
+        let (d1, d2, d3) = (
+            NaiveDate::from_ymd_opt(2025, 11, 3).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 11, 10).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 11, 5).unwrap(),
+        );
+        let id = j.add_bullet(d1, "Renew passport", None, &[], &[])?.id.unwrap();
+        j.migrate_one(d1, d2, BulletRef::Id(id.clone()))?;
+        j.migrate_one(d2, d3, BulletRef::Id(id.clone()))?;
+
+        let chain: Vec<NaiveDate> = j.history(&id)?.iter().map(|h| h.date).collect();
+        assert_eq!(chain, vec![d1, d2, d3], "History should follow the arrows, not the calendar");
+        let last = j.history(&id)?.pop().unwrap();
+        assert!(last.bullet.is_open(), "The last hop is the live bullet");
+        assert_eq!(last.bullet.migrated_from, Some(d2), "Last hop should point back");
+        Ok(())
+    }
+
+    #[test]
+    fn test_search_covers_every_page() -> Result<()> {
+        let env = TestEnv::new();
+        let j = &env.journal;
+        let (day, month) = (NaiveDate::from_ymd_opt(2025, 11, 3).unwrap(), NaiveDate::from_ymd_opt(2025, 12, 1).unwrap());
+        j.add_bullet(day, "Book flights", None, &[], &[])?;
+        j.add_to_month(month, Kind::Task, "Book hotel", None, &[], &[])?;
+        j.new_collection("Trips")?;
+        j.add_to_collection("Trips", Kind::Note, "Book a guide", None, &[], &[])?;
+
+        let pages: Vec<Page> = j.search(&Query::parse("book")?)?.into_iter().map(|h| h.page).collect();
+        assert_eq!(pages, vec![Page::Day, Page::Month, Page::Collection("Trips".into())], "Search should cover month pages and collections");
+        let windowed: Vec<Page> = j.search(&Query::parse("book after:2025-11-30")?)?.into_iter().map(|h| h.page).collect();
+        assert_eq!(windowed, vec![Page::Month], "A date window keeps month pages by their first day and skips collections");
+        Ok(())
+    }
+}
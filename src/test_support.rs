@@ -0,0 +1,52 @@
+//! Shared fixtures for unit tests.
+
+use std::env;
+use std::fs;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use chrono::NaiveDateTime;
+
+use crate::clock::Clock;
+use crate::journal::Journal;
+
+static TEST_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// A journal in a fresh temp directory, removed on drop.
+pub(crate) struct TestEnv {
+    root: std::path::PathBuf,
+    pub(crate) journal: Journal,
+}
+
+impl TestEnv {
+    pub(crate) fn new() -> Self {
+        // Create unique test directory using test counter
+        let mut test_root = env::temp_dir();
+        let test_num = TEST_COUNTER.fetch_add(1, Ordering::SeqCst);
+        let uniq = format!("bj_test_{}_{}",
+            std::process::id(),
+            test_num);
+        test_root.push(uniq);
+        let data = test_root.join("data").join("bullet_journal");
+        fs::create_dir_all(&data).expect("create data dir");
+        let journal = Journal::open(data).expect("open journal");
+
+        TestEnv {
+            root: test_root,
+            journal,
+        }
+    }
+
+    /// A fresh journal whose clock is stopped at `now`.
+    pub(crate) fn at(now: NaiveDateTime) -> Self {
+        let mut env = Self::new();
+        env.journal = env.journal.clone().with_clock(Clock::fixed(now));
+        env
+    }
+}
+
+impl Drop for TestEnv {
+    fn drop(&mut self) {
+        // Clean up test directory
+        fs::remove_dir_all(&self.root).ok();
+    }
+}
@@ -0,0 +1,146 @@
+//! How the terminal views look: glyphs, colours, date formats and week
+//! layout, from the `[display]` and `[colors]` sections of the config.
+
+use std::sync::OnceLock;
+
+use anyhow::Result;
+use chrono::{Datelike, Days, NaiveDate, Weekday};
+use colored::Color;
+
+use bullet_journal::{Config, Glyphs};
+
+static THEME: OnceLock<Theme> = OnceLock::new();
+
+pub struct Theme {
+	pub glyphs: GlyphSet,
+	pub colors: Colors,
+	pub date_format: String,
+	pub heading_format: String,
+	pub day_format: String,
+	pub box_width: usize,
+	pub week_start: Weekday,
+}
+
+/// Symbols shared by every view. Decorations that sit before text carry
+/// their own trailing space so a set can leave them out.
+pub struct GlyphSet {
+	pub open: &'static str,
+	pub done: &'static str,
+	pub migrated: &'static str,
+	pub scheduled: &'static str,
+	pub cancelled: &'static str,
+	pub event: &'static str,
+	pub note: &'static str,
+	/// High, medium and low priority, and the blank of the same width.
+	pub priority: [&'static str; 4],
+	pub recurring: &'static str,
+	pub clock: &'static str,
+	pub calendar: &'static str,
+	pub empty: &'static str,
+	pub check: &'static str,
+	pub meetings: &'static str,
+	pub from: &'static str,
+	pub to: &'static str,
+	pub continues: &'static str,
+	pub bar: [&'static str; 2],
+	pub branch: &'static str,
+	pub last_branch: &'static str,
+	pub today: &'static str,
+	pub day: &'static str,
+	pub dot: &'static str,
+	/// Box corners (top-left, top-right, bottom-left, bottom-right), then
+	/// horizontal, vertical, and the left and right tees.
+	pub frame: [&'static str; 8],
+}
+
+pub struct Colors {
+	pub accent: Color,
+	pub title: Color,
+	pub tag: Color,
+	pub muted: Color,
+	pub done: Color,
+	pub migrated: Color,
+	pub cancelled: Color,
+	pub event: Color,
+	pub high: Color,
+	pub medium: Color,
+	pub low: Color,
+	pub warning: Color,
+	pub weekend: Color,
+}
+
+const UNICODE: GlyphSet = GlyphSet {
+	open: "○", done: "●", migrated: "›", scheduled: "‹", cancelled: "✕", event: "◎", note: "–",
+	priority: ["▲", "▵", "▽", " "],
+	recurring: "↻", clock: "🕒 ", calendar: "📅 ", empty: "📭 ", check: "✓ ", meetings: "🗓 ",
+	from: "←", to: "→", continues: "…", bar: ["━", "━"], branch: "├──", last_branch: "└──",
+	today: "●", day: "○", dot: "•",
+	frame: ["╭", "╮", "╰", "╯", "─", "│", "├", "┤"],
+};
+
+const EMOJI: GlyphSet = GlyphSet {
+	open: "⬜", done: "✅", migrated: "⏩", scheduled: "⏪", cancelled: "❌", event: "📌", note: "📝",
+	priority: ["🔥", "⚡", "💤", "  "],
+	recurring: "🔁",
+	..UNICODE
+};
+
+const ASCII: GlyphSet = GlyphSet {
+	open: ".", done: "x", migrated: ">", scheduled: "<", cancelled: "~", event: "o", note: "-",
+	priority: ["!", "+", "-", " "],
+	recurring: "(r)", clock: "", calendar: "", empty: "", check: "", meetings: "",
+	from: "<-", to: "->", continues: "..", bar: ["#", "-"], branch: "|--", last_branch: "`--",
+	today: "*", day: "-", dot: "*",
+	frame: ["+", "+", "+", "+", "-", "|", "+", "+"],
+};
+
+/// Read the theme from `config` for the rest of the process.
+pub fn init(config: &Config) -> Result<()> {
+	if config.display.color == Some(false) { colored::control::set_override(false); }
+	let _ = THEME.set(build(config)?);
+	Ok(())
+}
+
+/// The theme read by [`init`], or the built-in look before that.
+pub fn theme() -> &'static Theme {
+	THEME.get_or_init(|| build(&Config::default()).expect("default config is valid"))
+}
+
+fn build(config: &Config) -> Result<Theme> {
+	let color = |role: &str, default: Color| config.color(role).unwrap_or(default);
+	let d = &config.display;
+	Ok(Theme {
+		glyphs: match d.glyphs { Glyphs::Unicode => UNICODE, Glyphs::Emoji => EMOJI, Glyphs::Ascii => ASCII },
+		colors: Colors {
+			accent: color("accent", Color::Cyan),
+			title: color("title", Color::Magenta),
+			tag: color("tag", Color::Blue),
+			muted: color("muted", Color::BrightBlack),
+			done: color("done", Color::Green),
+			migrated: color("migrated", Color::Blue),
+			cancelled: color("cancelled", Color::Red),
+			event: color("event", Color::Magenta),
+			high: color("high", Color::Red),
+			medium: color("medium", Color::Yellow),
+			low: color("low", Color::Green),
+			warning: color("warning", Color::Yellow),
+			weekend: color("weekend", Color::BrightBlue),
+		},
+		date_format: d.date_format.clone().unwrap_or_else(|| "%Y-%m-%d".to_string()),
+		heading_format: d.heading_format.clone().unwrap_or_else(|| "%A, %B %d".to_string()),
+		day_format: d.day_format.clone().unwrap_or_else(|| "%A, %b %d".to_string()),
+		box_width: d.box_width.unwrap_or(50),
+		week_start: config.week_start()?,
+	})
+}
+
+impl Theme {
+	pub fn date(&self, d: NaiveDate) -> String {
+		d.format(&self.date_format).to_string()
+	}
+
+	/// First day of the week containing `d`.
+	pub fn week_of(&self, d: NaiveDate) -> NaiveDate {
+		d - Days::new(d.weekday().days_since(self.week_start) as u64)
+	}
+}
@@ -0,0 +1,540 @@
+//! Full-screen journal (`bj tui`): move between days and weeks and edit
+//! bullets in place, drawn with the same glyphs and colours as `bj list`.
+
+use anyhow::{bail, Result};
+use chrono::{Days, NaiveDate};
+use colored::{ColoredString, Colorize, Styles};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::{Block, Borders, Gauge, List, ListItem, ListState, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+
+use bullet_journal::{parse_date_at, parse_text_meta_only, Bullet, BulletRef, Config, Journal, State};
+
+use crate::theme::theme;
+use crate::{branch, bullet_text, glyph, meeting_clock, priority_icon, trailer};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum View { Day, Week }
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Prompt { Add, Edit, Migrate, Delete }
+
+enum Row {
+	/// Day header in the week view, with done/total counts.
+	Day(NaiveDate, usize, usize),
+	Bullet(NaiveDate, Box<Bullet>),
+}
+
+struct App<'a> {
+	journal: &'a Journal,
+	config: &'a Config,
+	date: NaiveDate,
+	view: View,
+	rows: Vec<Row>,
+	selected: Option<usize>,
+	prompt: Option<(Prompt, String)>,
+	status: String,
+}
+
+pub fn run(journal: &Journal, config: &Config, date: NaiveDate) -> Result<()> {
+	let mut app = App::new(journal, config, date)?;
+	let mut terminal = ratatui::init();
+	let result = app.event_loop(&mut terminal);
+	ratatui::restore();
+	result
+}
+
+impl<'a> App<'a> {
+	fn new(journal: &'a Journal, config: &'a Config, date: NaiveDate) -> Result<Self> {
+		let mut app = App { journal, config, date, view: View::Day, rows: Vec::new(), selected: None, prompt: None, status: String::new() };
+		app.reload()?;
+		Ok(app)
+	}
+
+	fn event_loop(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+		loop {
+			terminal.draw(|f| self.draw(f))?;
+			let Event::Key(key) = event::read()? else { continue };
+			if key.kind != KeyEventKind::Press { continue; }
+			if !self.key(key.code) { return Ok(()); }
+		}
+	}
+
+	/// Apply one key press; errors end up in the status line. Returns false
+	/// when the key quits.
+	fn key(&mut self, code: KeyCode) -> bool {
+		if self.prompt.is_some() {
+			self.prompt_key(code);
+			return true;
+		}
+		self.status.clear();
+		let step = if self.view == View::Week { 7 } else { 1 };
+		let outcome = match code {
+			KeyCode::Char('q') | KeyCode::Esc => return false,
+			KeyCode::Left | KeyCode::Char('h') => self.go(self.date - Days::new(step)),
+			KeyCode::Right | KeyCode::Char('l') => self.go(self.date + Days::new(step)),
+			KeyCode::Char('t') => self.go(self.journal.clock().today()),
+			KeyCode::Tab | KeyCode::Char('w') => {
+				self.view = if self.view == View::Day { View::Week } else { View::Day };
+				self.selected = None;
+				self.reload()
+			}
+			KeyCode::Up | KeyCode::Char('k') => { self.step(-1); Ok(()) }
+			KeyCode::Down | KeyCode::Char('j') => { self.step(1); Ok(()) }
+			KeyCode::Char(' ') | KeyCode::Char('x') => self.toggle_done(),
+			KeyCode::Char('c') => self.with_selected(|j, date, b| j.cancel_bullet(date, bref(b)).map(drop)),
+			KeyCode::Char('+') => self.with_selected(|j, date, b| j.update_bullet(date, bref(b), |b| b.priority = Some(b.priority.map_or(1, |p| (p + 1).min(3)))).map(drop)),
+			KeyCode::Char('-') => self.with_selected(|j, date, b| j.update_bullet(date, bref(b), |b| b.priority = b.priority.and_then(|p| p.checked_sub(1)).filter(|p| *p > 0)).map(drop)),
+			KeyCode::Char('a') => { self.prompt = Some((Prompt::Add, String::new())); Ok(()) }
+			KeyCode::Char('e') => {
+				if let Some((_, b)) = self.current() {
+					let tags: String = b.tags.iter().map(|t| format!(" #{}", t)).collect();
+					self.prompt = Some((Prompt::Edit, format!("{}{}", b.text, tags)));
+				}
+				Ok(())
+			}
+			KeyCode::Char('m') => {
+				if let Some((date, _)) = self.current() {
+					let today = self.journal.clock().today();
+					let to = if date < today { today } else { date + Days::new(1) };
+					self.prompt = Some((Prompt::Migrate, to.to_string()));
+				}
+				Ok(())
+			}
+			KeyCode::Char('d') | KeyCode::Delete => {
+				if self.current().is_some() { self.prompt = Some((Prompt::Delete, String::new())); }
+				Ok(())
+			}
+			_ => Ok(()),
+		};
+		if let Err(e) = outcome { self.status = e.to_string(); }
+		true
+	}
+
+	fn prompt_key(&mut self, code: KeyCode) {
+		let Some((prompt, input)) = self.prompt.as_mut() else { return };
+		let prompt = *prompt;
+		match code {
+			KeyCode::Esc => self.prompt = None,
+			KeyCode::Char(c) if prompt == Prompt::Delete => {
+				self.prompt = None;
+				if c == 'y' {
+					if let Err(e) = self.with_selected(|j, date, b| j.delete_bullet(date, bref(b)).map(drop)) { self.status = e.to_string(); }
+				}
+			}
+			KeyCode::Char(c) => input.push(c),
+			KeyCode::Backspace => { input.pop(); }
+			KeyCode::Enter => {
+				let input = std::mem::take(input);
+				self.prompt = None;
+				if let Err(e) = self.submit(prompt, input.trim()) { self.status = e.to_string(); }
+			}
+			_ => {}
+		}
+	}
+
+	fn submit(&mut self, prompt: Prompt, input: &str) -> Result<()> {
+		if input.is_empty() { return Ok(()); }
+		match prompt {
+			Prompt::Add => {
+				let date = self.current().map_or(self.date, |(d, _)| d);
+				let (text, pr, tags) = parse_text_meta_only(input);
+				let pr = match pr { Some(p) => Some(p), None => self.config.default_priority()? };
+				let tags = self.config.with_default_tags(&tags);
+				self.journal.operation(&format!("tui: add to {}", date), || self.journal.add_bullet(date, &text, pr, &tags, &[]))?;
+				self.reload()?;
+				self.select_last_on(date);
+				Ok(())
+			}
+			Prompt::Edit => {
+				let (text, pr, tags) = parse_text_meta_only(input);
+				self.with_selected(|j, date, b| j.update_bullet(date, bref(b), |b| {
+					b.text = text;
+					b.tags = tags;
+					if pr.is_some() { b.priority = pr; }
+				}).map(drop))
+			}
+			Prompt::Migrate => {
+				let to = parse_date_at(input, self.journal.clock().today())?;
+				self.with_selected(|j, date, b| j.migrate_one(date, to, bref(b)).map(drop))?;
+				self.status = format!("Migrated to {}", to);
+				Ok(())
+			}
+			Prompt::Delete => Ok(()),
+		}
+	}
+
+	/// Run `action` on the selected bullet and refresh.
+	fn with_selected(&mut self, action: impl FnOnce(&Journal, NaiveDate, &Bullet) -> Result<()>) -> Result<()> {
+		let Some((date, b)) = self.current() else { return Ok(()) };
+		let b = b.clone();
+		let label = format!("tui: {} on {}", bref(&b), date);
+		self.journal.operation(&label, || action(self.journal, date, &b))?;
+		self.reload()
+	}
+
+	fn toggle_done(&mut self) -> Result<()> {
+		let Some((_, b)) = self.current() else { return Ok(()) };
+		if !b.is_actionable() { bail!("only open tasks can be completed"); }
+		let reopen = b.is_done();
+		self.with_selected(|j, date, b| {
+			if reopen { j.update_bullet(date, bref(b), |b| b.state = State::Open).map(drop) } else { j.mark_done(date, bref(b)).map(drop) }
+		})
+	}
+
+	fn go(&mut self, date: NaiveDate) -> Result<()> {
+		self.date = date;
+		self.selected = None;
+		self.reload()
+	}
+
+	fn current(&self) -> Option<(NaiveDate, &Bullet)> {
+		match self.rows.get(self.selected?)? {
+			Row::Bullet(date, b) => Some((*date, b)),
+			Row::Day(..) => None,
+		}
+	}
+
+	fn days(&self) -> Vec<NaiveDate> {
+		match self.view {
+			View::Day => vec![self.date],
+			View::Week => {
+				let start = theme().week_of(self.date);
+				(0..7).map(|i| start + Days::new(i)).collect()
+			}
+		}
+	}
+
+	fn reload(&mut self) -> Result<()> {
+		let mut rows = Vec::new();
+		for day in self.days() {
+			let bullets = self.journal.bullets(day)?;
+			if self.view == View::Week {
+				let total = bullets.iter().filter(|b| b.is_actionable()).count();
+				let done = bullets.iter().filter(|b| b.is_done()).count();
+				rows.push(Row::Day(day, done, total));
+			}
+			rows.extend(bullets.into_iter().map(|b| Row::Bullet(day, Box::new(b))));
+		}
+		self.rows = rows;
+		// Stay on the same row where possible, landing on the nearest bullet.
+		let start = self.selected.unwrap_or(0).min(self.rows.len().saturating_sub(1));
+		self.selected = (start..self.rows.len()).chain((0..start).rev()).find(|&i| matches!(self.rows[i], Row::Bullet(..)));
+		Ok(())
+	}
+
+	fn step(&mut self, delta: isize) {
+		let Some(mut i) = self.selected else { return };
+		loop {
+			let Some(next) = i.checked_add_signed(delta).filter(|n| *n < self.rows.len()) else { return };
+			i = next;
+			if matches!(self.rows[i], Row::Bullet(..)) { self.selected = Some(i); return; }
+		}
+	}
+
+	fn select_last_on(&mut self, date: NaiveDate) {
+		if let Some(i) = self.rows.iter().rposition(|r| matches!(r, Row::Bullet(d, _) if *d == date)) {
+			self.selected = Some(i);
+		}
+	}
+
+	fn draw(&self, f: &mut Frame) {
+		let [header, body, footer] = Layout::vertical([Constraint::Length(4), Constraint::Min(1), Constraint::Length(1)]).areas(f.area());
+
+		let bullets: Vec<&Bullet> = self.rows.iter().filter_map(|r| match r { Row::Bullet(_, b) => Some(&**b), _ => None }).collect();
+		let total = bullets.iter().filter(|b| b.is_actionable()).count();
+		let completed = bullets.iter().filter(|b| b.is_done()).count();
+		let meetings = bullets.iter().filter(|b| b.meeting_time.is_some()).count();
+		let (g, c) = (&theme().glyphs, &theme().colors);
+		let title = match self.view {
+			View::Day => format!(" {}{} ", g.calendar, day_label(self.date, self.journal.clock().today())),
+			View::Week => {
+				let days = self.days();
+				format!(" Week: {} - {} ", theme().date(days[0]), theme().date(days[6]))
+			}
+		};
+		let block = Block::default()
+			.borders(Borders::ALL)
+			.border_style(Style::default().fg(color(c.muted)))
+			.title(Span::styled(" BULLET JOURNAL ", Style::default().fg(color(c.title)).add_modifier(Modifier::BOLD)))
+			.title(Span::styled(title, Style::default().fg(color(c.accent)).add_modifier(Modifier::BOLD)));
+		let inner = block.inner(header);
+		f.render_widget(block, header);
+		let [gauge, summary] = Layout::vertical([Constraint::Length(1), Constraint::Length(1)]).areas(inner);
+		let ratio = if total > 0 { completed as f64 / total as f64 } else { 0.0 };
+		f.render_widget(Gauge::default().gauge_style(Style::default().fg(color(c.done)).bg(color(c.muted))).ratio(ratio).label(format!("{}%", (ratio * 100.0) as usize)), gauge);
+		let summary_text = format!("{}{}/{} done  {}  {}{} mtgs", g.check, completed, total, g.dot, g.meetings, meetings);
+		f.render_widget(Paragraph::new(summary_text).style(Style::default().add_modifier(Modifier::ITALIC | Modifier::DIM)), summary);
+
+		let items: Vec<ListItem> = self.rows.iter().map(|r| match r {
+			Row::Day(d, done, total) => day_row(*d, self.journal.clock().today(), *done, *total),
+			Row::Bullet(date, b) => bullet_row(*date, b),
+		}).collect();
+		let empty = items.is_empty();
+		let list = List::new(items).highlight_style(Style::default().bg(Color::Indexed(236)));
+		let mut state = ListState::default().with_selected(self.selected);
+		if empty {
+			f.render_widget(Paragraph::new(format!("{}No bullets for {}  -  press a to add one", g.empty, theme().date(self.date))).style(Style::default().add_modifier(Modifier::DIM)), body);
+		} else {
+			f.render_stateful_widget(list, body, &mut state);
+		}
+
+		let footer_line = match &self.prompt {
+			Some((Prompt::Delete, _)) => Line::from(Span::styled("Delete this bullet? (y/n)", Style::default().fg(color(c.cancelled)))),
+			Some((p, input)) => {
+				let label = match p { Prompt::Add => "Add", Prompt::Edit => "Edit", _ => "Migrate to" };
+				Line::from(vec![Span::styled(format!("{}: ", label), Style::default().fg(color(c.accent))), Span::raw(input.clone()), Span::styled("█", Style::default().fg(color(c.muted)))])
+			}
+			None if !self.status.is_empty() => Line::from(Span::styled(self.status.clone(), Style::default().fg(color(c.warning)))),
+			None => Line::from(Span::styled(
+				"←/→ day  tab week  ↑/↓ move  space done  a add  e edit  d delete  m migrate  c cancel  +/- priority  t today  q quit",
+				Style::default().fg(color(c.muted)),
+			)),
+		};
+		f.render_widget(Paragraph::new(footer_line), footer);
+	}
+}
+
+fn bref(b: &Bullet) -> BulletRef {
+	b.id.clone().map(BulletRef::Id).unwrap_or(BulletRef::Index(b.visible_index))
+}
+
+fn day_label(date: NaiveDate, today: NaiveDate) -> String {
+	if date == today {
+		"Today".to_string()
+	} else if Some(date) == today.pred_opt() {
+		"Yesterday".to_string()
+	} else if Some(date) == today.succ_opt() {
+		"Tomorrow".to_string()
+	} else {
+		date.format(&theme().heading_format).to_string()
+	}
+}
+
+fn day_row(date: NaiveDate, today: NaiveDate, done: usize, total: usize) -> ListItem<'static> {
+	let (t, g, c) = (theme(), &theme().glyphs, &theme().colors);
+	let bars = 10;
+	let filled = (done * bars).checked_div(total).unwrap_or(0);
+	let header = date.format(&t.day_format).to_string();
+	let header = if date == today { header.bold().black().on_color(c.accent) } else { header.bold().color(c.accent) };
+	ListItem::new(Line::from(vec![
+		Span::raw(" "),
+		span(header),
+		Span::raw("  "),
+		span(g.bar[0].repeat(filled).color(c.done)),
+		span(g.bar[1].repeat(bars - filled).color(c.muted)),
+		span(format!(" {}/{}", done, total).dimmed()),
+	]))
+}
+
+fn bullet_row(date: NaiveDate, b: &Bullet) -> ListItem<'static> {
+	let c = &theme().colors;
+	let time = meeting_clock(date, b).map(|t| format!("{} ", t)).unwrap_or_else(|| "      ".to_string());
+	let tags = if b.tags.is_empty() { String::new() } else { format!("  {}", b.tags.join(" ")) };
+	let id = trailer(b);
+	let mut lines = vec![Line::from(vec![
+		span(format!(" {:>2} ", b.visible_index).dimmed()),
+		span(glyph(b)),
+		Span::raw(" "),
+		span(priority_icon(b)),
+		Span::raw(" "),
+		span(time.color(c.accent)),
+		span(bullet_text(b, true)),
+		span(tags.color(c.tag).italic()),
+		span(id.color(c.muted)),
+	])];
+	for (i, n) in b.notes.iter().enumerate() {
+		lines.push(Line::from(vec![Span::raw("       "), span(branch(i, b.notes.len())), Span::raw(" "), span(n.dimmed())]));
+	}
+	ListItem::new(Text::from(lines))
+}
+
+/// Carry a `colored` string's colour and style over to a ratatui span.
+fn span(s: ColoredString) -> Span<'static> {
+	let mut style = Style::default();
+	if let Some(fg) = s.fgcolor { style = style.fg(color(fg)); }
+	if let Some(bg) = s.bgcolor { style = style.bg(color(bg)); }
+	let modifiers = [
+		(Styles::Bold, Modifier::BOLD),
+		(Styles::Dimmed, Modifier::DIM),
+		(Styles::Italic, Modifier::ITALIC),
+		(Styles::Underline, Modifier::UNDERLINED),
+		(Styles::Strikethrough, Modifier::CROSSED_OUT),
+	];
+	for (from, to) in modifiers {
+		if s.style.contains(from) { style = style.add_modifier(to); }
+	}
+	Span::styled(String::from(&*s), style)
+}
+
+fn color(c: colored::Color) -> Color {
+	use colored::Color as C;
+	if !colored::control::SHOULD_COLORIZE.should_colorize() { return Color::Reset; }
+	match c {
+		C::Black => Color::Black,
+		C::Red => Color::Red,
+		C::Green => Color::Green,
+		C::Yellow => Color::Yellow,
+		C::Blue => Color::Blue,
+		C::Magenta => Color::Magenta,
+		C::Cyan => Color::Cyan,
+		C::White => Color::Gray,
+		C::BrightBlack => Color::DarkGray,
+		C::BrightRed => Color::LightRed,
+		C::BrightGreen => Color::LightGreen,
+		C::BrightYellow => Color::LightYellow,
+		C::BrightBlue => Color::LightBlue,
+		C::BrightMagenta => Color::LightMagenta,
+		C::BrightCyan => Color::LightCyan,
+		C::BrightWhite => Color::White,
+		C::TrueColor { r, g, b } => Color::Rgb(r, g, b),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use bullet_journal::{Clock, Kind};
+
+    fn d(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 11, day).unwrap()
+    }
+
+    /// A journal in a fresh temp directory with the clock stopped on the
+    /// morning of the 19th, removed on drop.
+    struct TestEnv {
+        root: PathBuf,
+        journal: Journal,
+    }
+
+    impl TestEnv {
+        fn new() -> Self {
+            static COUNTER: AtomicUsize = AtomicUsize::new(0);
+            let root = std::env::temp_dir().join(format!("bj_tui_test_{}_{}", std::process::id(), COUNTER.fetch_add(1, Ordering::SeqCst)));
+            let journal = Journal::open(&root).expect("open journal").with_clock(Clock::fixed(d(19).and_hms_opt(9, 0, 0).unwrap()));
+            TestEnv { root, journal }
+        }
+    }
+
+    impl Drop for TestEnv {
+        fn drop(&mut self) {
+            std::fs::remove_dir_all(&self.root).ok();
+        }
+    }
+
+    fn press(app: &mut App, keys: &str) {
+        for c in keys.chars() {
+            let code = match c {
+                '\n' => KeyCode::Enter,
+                '\t' => KeyCode::Tab,
+                c => KeyCode::Char(c),
+            };
+            assert!(app.key(code), "{:?} should not quit", c);
+        }
+    }
+
+    fn texts(app: &App) -> Vec<String> {
+        app.rows.iter().filter_map(|r| match r { Row::Bullet(_, b) => Some(b.text.clone()), _ => None }).collect()
+    }
+
+    #[test]
+    fn test_switch_views_and_move() -> Result<()> {
+        let env = TestEnv::new();
+        let j = &env.journal;
+        let config = Config::default();
+        j.add_bullet(d(19), "Today A", None, &[], &[])?;
+        j.add_bullet(d(19), "Today B", None, &[], &[])?;
+        j.add_bullet(d(20), "Tomorrow", None, &[], &[])?;
+
+        let mut app = App::new(j, &config, d(19))?;
+        assert_eq!(texts(&app), ["Today A", "Today B"]);
+        assert_eq!(app.current().unwrap().1.text, "Today A", "The first bullet starts selected");
+        press(&mut app, "jj");
+        assert_eq!(app.current().unwrap().1.text, "Today B", "Moving stops at the last bullet");
+
+        press(&mut app, "l");
+        assert_eq!((app.date, texts(&app)), (d(20), vec!["Tomorrow".to_string()]));
+        press(&mut app, "\t");
+        assert!(app.view == View::Week);
+        assert_eq!(texts(&app), ["Today A", "Today B", "Tomorrow"], "The week shows every day");
+        assert!(matches!(app.rows[0], Row::Day(..)) && app.current().is_some(), "Day headers are never selected");
+        press(&mut app, "h");
+        assert_eq!(app.date, d(13), "Left steps a week in the week view");
+        press(&mut app, "wt");
+        assert!(app.view == View::Day && app.date == d(19));
+        assert!(!app.key(KeyCode::Char('q')));
+        Ok(())
+    }
+
+    #[test]
+    fn test_prompts_edit_the_journal() -> Result<()> {
+        let env = TestEnv::new();
+        let j = &env.journal;
+        let config = Config::default();
+        let mut app = App::new(j, &config, d(19))?;
+
+        press(&mut app, "aWrite report #work\n");
+        press(&mut app, "aCall Bob\n");
+        assert_eq!(j.bullets(d(19))?.iter().map(|b| (b.text.as_str(), b.tags.len())).collect::<Vec<_>>(), [("Write report", 1), ("Call Bob", 0)]);
+        assert_eq!(app.current().unwrap().1.text, "Call Bob", "A new bullet is selected");
+
+        app.key(KeyCode::Char('e'));
+        assert_eq!(app.prompt.as_ref().map(|(_, input)| input.as_str()), Some("Call Bob"), "Edit starts from the bullet");
+        app.key(KeyCode::Backspace);
+        press(&mut app, "x\n");
+        assert_eq!(j.bullets(d(19))?[1].text, "Call Box");
+
+        press(&mut app, "m");
+        assert_eq!(app.prompt.as_ref().map(|(_, input)| input.as_str()), Some("2025-11-20"), "Migrate suggests the next day");
+        press(&mut app, "\n");
+        assert_eq!(j.bullets(d(20))?[0].text, "Call Box");
+        assert_eq!(j.bullets(d(19))?[1].state, State::Migrated);
+        assert_eq!(app.status, "Migrated to 2025-11-20");
+
+        press(&mut app, "k");
+        app.key(KeyCode::Delete);
+        press(&mut app, "n");
+        assert_eq!(j.bullets(d(19))?.len(), 2, "Anything but y keeps the bullet");
+        app.key(KeyCode::Delete);
+        press(&mut app, "y");
+        assert_eq!(texts(&app), ["Call Box"], "Only the migrated marker is left");
+
+        app.key(KeyCode::Char('a'));
+        app.key(KeyCode::Esc);
+        assert!(app.prompt.is_none() && j.bullets(d(19))?.len() == 1, "Esc cancels a prompt");
+        Ok(())
+    }
+
+    #[test]
+    fn test_done_toggle_and_priority() -> Result<()> {
+        let env = TestEnv::new();
+        let j = &env.journal;
+        let config = Config::default();
+        j.add_bullet(d(19), "Task", Some(3), &[], &[])?;
+        j.add_entry(d(19), Kind::Note, "Just a note", None, &[], &[])?;
+        let mut app = App::new(j, &config, d(19))?;
+
+        press(&mut app, " ");
+        assert!(j.bullets(d(19))?[0].is_done());
+        press(&mut app, "x");
+        assert!(j.bullets(d(19))?[0].is_open(), "Toggling again reopens");
+
+        press(&mut app, "+");
+        assert_eq!(j.bullets(d(19))?[0].priority, Some(3), "Priority stops at high");
+        press(&mut app, "---");
+        assert_eq!(j.bullets(d(19))?[0].priority, None);
+        press(&mut app, "-+");
+        assert_eq!(j.bullets(d(19))?[0].priority, Some(1));
+
+        press(&mut app, "j ");
+        assert_eq!(app.status, "only open tasks can be completed", "Errors show in the status line");
+        press(&mut app, "k");
+        assert!(app.status.is_empty(), "The next key clears the status");
+        Ok(())
+    }
+}
@@ -0,0 +1,259 @@
+//! Undo/redo log of journal changes.
+//!
+//! Every change is recorded under `.undo/` in the journal root as a numbered
+//! directory holding the label, the names of the files it touched, and each
+//! file's contents before and after (`before/NAME`, `after/NAME`; a missing
+//! copy means the file did not exist). `.undo/position` counts the
+//! operations currently applied; ones above it can be redone until a new
+//! change is recorded.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use chrono::NaiveDateTime;
+
+use crate::collection::COLLECTIONS_DIR;
+use crate::journal::{write_atomic, write_file_lines, Journal};
+
+/// How many operations are kept; older ones can no longer be undone.
+const MAX_OPERATIONS: u64 = 100;
+
+/// An operation being recorded: the label and each touched file's contents
+/// before its first write.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Pending {
+	label: String,
+	before: BTreeMap<String, Option<String>>,
+}
+
+/// A recorded change, as reported by [`Journal::undo`] and [`Journal::redo`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Operation {
+	pub label: String,
+	pub at: NaiveDateTime,
+	/// File names relative to the journal root.
+	pub files: Vec<String>,
+}
+
+impl Journal {
+	/// Run `f` as one undoable operation: every file it writes is
+	/// snapshotted before and after. Nested calls join the outer operation.
+	pub fn operation<T>(&self, label: &str, f: impl FnOnce() -> Result<T>) -> Result<T> {
+		if self.pending.borrow().is_some() { return f(); }
+		let _lock = self.lock()?;
+		*self.pending.borrow_mut() = Some(Pending { label: label.to_string(), ..Pending::default() });
+		let result = f();
+		let pending = self.pending.borrow_mut().take().expect("operation in progress");
+		self.record(pending)?;
+		result
+	}
+
+	/// Write a journal file, recording it in the current operation (or in
+	/// an operation of its own).
+	pub(crate) fn write_lines(&self, path: &Path, lines: &[String]) -> Result<()> {
+		if self.pending.borrow().is_none() {
+			let name = path.file_name().map_or_else(String::new, |n| n.to_string_lossy().into_owned());
+			return self.operation(&format!("write {}", name), || self.write_lines(path, lines));
+		}
+		if let Some(name) = self.tracked_name(path) {
+			let before = read_optional(path)?;
+			if let Some(p) = self.pending.borrow_mut().as_mut() { p.before.entry(name).or_insert(before); }
+		}
+		write_file_lines(path, lines)
+	}
+
+	/// Revert the most recent operation and return it, or `None` if there
+	/// is nothing to undo.
+	pub fn undo(&self) -> Result<Option<Operation>> {
+		let _lock = self.lock()?;
+		let position = self.undo_position()?;
+		if position == 0 { return Ok(None); }
+		let Some(op) = self.load(position)? else { return Ok(None) };
+		self.restore(position, &op, "after", "before")?;
+		self.set_undo_position(position - 1)?;
+		Ok(Some(op))
+	}
+
+	/// Re-apply the most recently undone operation and return it, or `None`
+	/// if there is nothing to redo.
+	pub fn redo(&self) -> Result<Option<Operation>> {
+		let _lock = self.lock()?;
+		let position = self.undo_position()? + 1;
+		let Some(op) = self.load(position)? else { return Ok(None) };
+		self.restore(position, &op, "before", "after")?;
+		self.set_undo_position(position)?;
+		Ok(Some(op))
+	}
+
+	/// Replace every file of operation `seq` with its `to` copy, after
+	/// checking nothing changed it since its `from` copy.
+	fn restore(&self, seq: u64, op: &Operation, from: &str, to: &str) -> Result<()> {
+		let dir = self.undo_dir().join(seq.to_string());
+		for name in &op.files {
+			if read_optional(&self.root().join(name))? != read_optional(&dir.join(from).join(name))? {
+				bail!("{} has changed since \"{}\"; not touching it", name, op.label);
+			}
+		}
+		for name in &op.files {
+			let path = self.root().join(name);
+			match read_optional(&dir.join(to).join(name))? {
+				Some(contents) => {
+					fs::create_dir_all(path.parent().expect("journal file"))?;
+					write_atomic(&path, contents.as_bytes())?
+				}
+				None => if path.exists() { fs::remove_file(&path).with_context(|| format!("remove {}", path.display()))?; },
+			}
+		}
+		Ok(())
+	}
+
+	/// Store a finished operation if it changed anything, dropping any
+	/// redo history and the oldest operations past [`MAX_OPERATIONS`].
+	fn record(&self, pending: Pending) -> Result<()> {
+		let mut files = Vec::new();
+		for (name, before) in &pending.before {
+			if read_optional(&self.root().join(name))? != *before { files.push(name.clone()); }
+		}
+		if files.is_empty() { return Ok(()); }
+
+		let position = self.undo_position()?;
+		for seq in self.operation_seqs()? {
+			let stale = seq > position || seq + MAX_OPERATIONS <= position + 1;
+			if stale { fs::remove_dir_all(self.undo_dir().join(seq.to_string())).ok(); }
+		}
+		let seq = position + 1;
+		let dir = self.undo_dir().join(seq.to_string());
+		fs::create_dir_all(dir.join("before"))?;
+		fs::create_dir_all(dir.join("after"))?;
+		for name in &files {
+			if let Some(before) = &pending.before[name] { save(&dir.join("before").join(name), before)?; }
+			if let Some(after) = read_optional(&self.root().join(name))? { save(&dir.join("after").join(name), &after)?; }
+		}
+		let mut meta = vec![pending.label, self.clock().now().format("%Y-%m-%dT%H:%M:%S").to_string()];
+		meta.extend(files);
+		write_file_lines(&dir.join("operation"), &meta)?;
+		self.set_undo_position(seq)
+	}
+
+	fn load(&self, seq: u64) -> Result<Option<Operation>> {
+		let path = self.undo_dir().join(seq.to_string()).join("operation");
+		let Some(meta) = read_optional(&path)? else { return Ok(None) };
+		let mut lines = meta.lines().map(str::to_string);
+		let label = lines.next().unwrap_or_default();
+		let at = lines.next().and_then(|s| NaiveDateTime::parse_from_str(&s, "%Y-%m-%dT%H:%M:%S").ok())
+			.with_context(|| format!("corrupt undo record {}", path.display()))?;
+		Ok(Some(Operation { label, at, files: lines.collect() }))
+	}
+
+	/// Name of a file the undo log tracks, relative to the journal root:
+	/// anything directly in the root or in its collections folder.
+	fn tracked_name(&self, path: &Path) -> Option<String> {
+		let name = path.file_name()?.to_str()?;
+		let dir = path.parent()?;
+		if dir == self.root() { return Some(name.to_string()); }
+		if dir == self.root().join(COLLECTIONS_DIR) { return Some(format!("{}/{}", COLLECTIONS_DIR, name)); }
+		None
+	}
+
+	fn operation_seqs(&self) -> Result<Vec<u64>> {
+		let dir = self.undo_dir();
+		if !dir.exists() { return Ok(Vec::new()); }
+		let mut seqs = Vec::new();
+		for entry in fs::read_dir(&dir).with_context(|| format!("read {}", dir.display()))? {
+			if let Some(seq) = entry?.file_name().to_str().and_then(|n| n.parse().ok()) { seqs.push(seq); }
+		}
+		Ok(seqs)
+	}
+
+	fn undo_dir(&self) -> PathBuf { self.root().join(".undo") }
+
+	fn undo_position(&self) -> Result<u64> {
+		Ok(read_optional(&self.undo_dir().join("position"))?.and_then(|s| s.trim().parse().ok()).unwrap_or(0))
+	}
+
+	fn set_undo_position(&self, position: u64) -> Result<()> {
+		fs::create_dir_all(self.undo_dir())?;
+		write_file_lines(&self.undo_dir().join("position"), &[position.to_string()])
+	}
+}
+
+/// Write a snapshot, creating the folder it sits in.
+fn save(path: &Path, contents: &str) -> Result<()> {
+	fs::create_dir_all(path.parent().expect("snapshot file"))?;
+	write_atomic(path, contents.as_bytes())
+}
+
+fn read_optional(path: &Path) -> Result<Option<String>> {
+	if !path.exists() { return Ok(None); }
+	fs::read_to_string(path).map(Some).with_context(|| format!("read {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bullet::BulletRef;
+    use crate::test_support::TestEnv;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_undo_redo_across_files() -> Result<()> {
+        let env = TestEnv::new();
+        let j = &env.journal;
+        let (d1, d2) = (NaiveDate::from_ymd_opt(2025, 11, 6).unwrap(), NaiveDate::from_ymd_opt(2025, 11, 7).unwrap());
+        j.add_bullet(d1, "First", None, &[], &[])?;
+        j.add_bullet(d1, "Second", None, &[], &[])?;
+        let before = fs::read_to_string(j.file_for(d1))?;
+
+        let op = j.operation("migrate", || j.migrate_open(d1, d2))?;
+        assert_eq!(op.len(), 2);
+        let migrated = (fs::read_to_string(j.file_for(d1))?, fs::read_to_string(j.file_for(d2))?);
+
+        let undone = j.undo()?.expect("something to undo");
+        assert_eq!((undone.label.as_str(), undone.files.len()), ("migrate", 2), "Both files belong to one operation");
+        assert_eq!(fs::read_to_string(j.file_for(d1))?, before, "Source day should be restored");
+        assert!(!j.file_for(d2).exists(), "A file the operation created should be removed");
+
+        let redone = j.redo()?.expect("something to redo");
+        assert_eq!(redone.label, "migrate");
+        assert_eq!((fs::read_to_string(j.file_for(d1))?, fs::read_to_string(j.file_for(d2))?), migrated);
+        assert!(j.redo()?.is_none(), "Nothing left to redo");
+
+        // Each write outside an operation is undone on its own, newest first.
+        j.undo()?;
+        j.undo()?;
+        assert_eq!(j.bullets(d1)?.len(), 1, "Second add should be undone");
+        j.undo()?;
+        assert!(j.undo()?.is_none(), "Nothing left to undo");
+        assert!(j.bullets(d1)?.is_empty());
+
+        // A new change drops the redo history.
+        j.add_bullet(d1, "Fresh", None, &[], &[])?;
+        assert!(j.redo()?.is_none(), "New changes should clear redo");
+        Ok(())
+    }
+
+    #[test]
+    fn test_undo_refuses_after_outside_edit() -> Result<()> {
+        let env = TestEnv::new();
+        let j = &env.journal;
+        let date = NaiveDate::from_ymd_opt(2025, 11, 6).unwrap();
+        let b = j.add_bullet(date, "Task", None, &[], &[])?;
+        j.mark_done(date, BulletRef::Id(b.id.unwrap()))?;
+        fs::write(j.file_for(date), "- [ ] Edited by hand\n")?;
+        assert!(j.undo().is_err(), "Undo should not discard edits made outside bj");
+        assert_eq!(fs::read_to_string(j.file_for(date))?, "- [ ] Edited by hand\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_failed_operation_keeps_no_record() -> Result<()> {
+        let env = TestEnv::new();
+        let j = &env.journal;
+        let date = NaiveDate::from_ymd_opt(2025, 11, 6).unwrap();
+        assert!(j.operation("done", || j.mark_done(date, 1)).is_err());
+        assert!(j.undo()?.is_none(), "An operation that wrote nothing is not recorded");
+        Ok(())
+    }
+}